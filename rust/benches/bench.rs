@@ -1,6 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use aoc2020::*;
+use aoc2020::registry;
+use aoc2020::year2020::*;
 
 macro_rules! bench {
     ($c:expr, $path:path) => {{
@@ -43,5 +44,37 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     bench!(c, day25);
 }
 
-criterion_group!(benches, criterion_benchmark);
+pub fn strategies_benchmark(c: &mut Criterion) {
+    // all variants go through the registry, so the timings include formatting the answer
+    for day in registry::days(2020) {
+        let s = (day.input)();
+        for strategy in day.strategies {
+            let name = format!("{}::part{}::{}", day.name, strategy.part, strategy.name);
+            c.bench_function(&name, |b| {
+                b.iter(|| black_box((strategy.solver)(black_box(s))))
+            });
+        }
+    }
+}
+
+pub fn simd_benchmark(c: &mut Criterion) {
+    // the vectorized day 2 solver checks both policies at once, on an input with more lines
+    let s = day02::input().strip_suffix(b"\n").unwrap().repeat(100);
+    c.bench_function("day02::x100::part1", |b| {
+        b.iter(|| black_box(day02::part1(black_box(&s))))
+    });
+    c.bench_function("day02::x100::part2", |b| {
+        b.iter(|| black_box(day02::part2(black_box(&s))))
+    });
+    c.bench_function("day02::x100::simd", |b| {
+        b.iter(|| black_box(day02::simd::count_valid(black_box(&s))))
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    strategies_benchmark,
+    simd_benchmark
+);
 criterion_main!(benches);
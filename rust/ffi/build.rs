@@ -0,0 +1,23 @@
+#[cfg(feature = "header")]
+fn main() {
+    use std::env;
+    use std::path::Path;
+
+    // only built with the `header` feature; the header is checked in for the C side
+    let dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    let config = cbindgen::Config::from_file(Path::new(&dir).join("cbindgen.toml")).unwrap();
+    cbindgen::Builder::new()
+        .with_crate(&dir)
+        .with_config(config)
+        .generate()
+        .expect("failed to generate the C header")
+        .write_to_file(Path::new(&dir).join("include/aoc2020.h"));
+}
+
+#[cfg(not(feature = "header"))]
+fn main() {
+    // nothing to do unless the header is being regenerated
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,82 @@
+#ifndef AOC2020_H
+#define AOC2020_H
+
+/* generated by cbindgen from src/lib.rs, do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status codes returned by the `aoc2020_*` functions.
+ */
+typedef enum Aoc2020Status {
+  AOC2020_STATUS_OK = 0,
+  /**
+   * A required pointer was null.
+   */
+  AOC2020_STATUS_NULL_POINTER = 1,
+  /**
+   * There's no solution for this day.
+   */
+  AOC2020_STATUS_UNKNOWN_DAY = 2,
+  /**
+   * The part is neither 1 nor 2.
+   */
+  AOC2020_STATUS_UNKNOWN_PART = 3,
+  /**
+   * The answer doesn't fit, `*out_len` holds the size needed.
+   */
+  AOC2020_STATUS_BUFFER_TOO_SMALL = 4,
+  /**
+   * The solver panicked, most likely on malformed input. Only reported if the library
+   * was built with `panic = "unwind"`; with the release profile the process aborts.
+   */
+  AOC2020_STATUS_PANIC = 5,
+} Aoc2020Status;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Solves a part (1 or 2) of a day (1 to 25) of 2020 for the input in `ptr[0..len]`
+ * (newline-terminated lines, like the puzzle inputs), and writes the answer into
+ * `out_buf`, without a trailing nul.
+ *
+ * On entry `*out_len` is the capacity of `out_buf`, on return it's the length of the
+ * answer; if the answer doesn't fit, nothing is written and `AOC2020_STATUS_BUFFER_TOO_SMALL`
+ * is returned, so the call can be retried with a large enough buffer.
+ *
+ * Never unwinds into the caller.
+ *
+ * # Safety
+ *
+ * `ptr` must be valid for reads of `len` bytes (it may be null if `len` is 0), `out_len`
+ * must be valid for reads and writes, and `out_buf` must be valid for writes of `*out_len`
+ * bytes.
+ */
+enum Aoc2020Status aoc2020_solve(uint32_t day,
+                                 uint32_t part,
+                                 const uint8_t *ptr,
+                                 size_t len,
+                                 uint8_t *out_buf,
+                                 size_t *out_len);
+
+/**
+ * Points `*out_ptr` / `*out_len` at the bundled puzzle input of a day; the data is static
+ * and must not be freed.
+ *
+ * # Safety
+ *
+ * `out_ptr` and `out_len` must be valid for writes.
+ */
+enum Aoc2020Status aoc2020_input(uint32_t day, const uint8_t **out_ptr, size_t *out_len);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* AOC2020_H */
@@ -0,0 +1,130 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, thread};
+
+use aoc2020::registry::{self, Solver};
+
+// some of the solvers keep multi-megabyte boards on the stack, so they don't run on the
+// caller's thread; same as the runner
+const SOLVER_STACK_SIZE: usize = 64 << 20;
+
+// doc comments here end up in the generated header
+
+/// Status codes returned by the `aoc2020_*` functions.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Aoc2020Status {
+    Ok = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// There's no solution for this day.
+    UnknownDay = 2,
+    /// The part is neither 1 nor 2.
+    UnknownPart = 3,
+    /// The answer doesn't fit, `*out_len` holds the size needed.
+    BufferTooSmall = 4,
+    /// The solver panicked, most likely on malformed input. Only reported if the library
+    /// was built with `panic = "unwind"`; with the release profile the process aborts.
+    Panic = 5,
+}
+
+use self::Aoc2020Status as Status;
+
+fn find_solver(day: u32, part: u32) -> Result<Solver, Status> {
+    let day = match day {
+        1..=25 => registry::find(registry::LATEST_YEAR, day as u8).ok_or(Status::UnknownDay)?,
+        _ => return Err(Status::UnknownDay),
+    };
+    match part {
+        1 | 2 => day.solver(part as u8).ok_or(Status::UnknownPart),
+        _ => Err(Status::UnknownPart),
+    }
+}
+
+fn solve(solver: Solver, s: &[u8]) -> Result<String, Status> {
+    let s = s.to_vec();
+    let handle = thread::Builder::new()
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || solver(&s))
+        .map_err(|_| Status::Panic)?;
+    handle.join().map_err(|_| Status::Panic)
+}
+
+/// Solves a part (1 or 2) of a day (1 to 25) of 2020 for the input in `ptr[0..len]`
+/// (newline-terminated lines, like the puzzle inputs), and writes the answer into
+/// `out_buf`, without a trailing nul.
+///
+/// On entry `*out_len` is the capacity of `out_buf`, on return it's the length of the
+/// answer; if the answer doesn't fit, nothing is written and `AOC2020_STATUS_BUFFER_TOO_SMALL`
+/// is returned, so the call can be retried with a large enough buffer.
+///
+/// Never unwinds into the caller.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads of `len` bytes (it may be null if `len` is 0), `out_len`
+/// must be valid for reads and writes, and `out_buf` must be valid for writes of `*out_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2020_solve(
+    day: u32,
+    part: u32,
+    ptr: *const u8,
+    len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> Aoc2020Status {
+    if out_len.is_null() || (ptr.is_null() && len != 0) {
+        return Status::NullPointer;
+    }
+    let s = if len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    };
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| solve(find_solver(day, part)?, s)));
+    let answer = match answer {
+        Ok(Ok(answer)) => answer,
+        Ok(Err(status)) => return status,
+        Err(_) => return Status::Panic,
+    };
+    let capacity = *out_len;
+    *out_len = answer.len();
+    if answer.len() > capacity {
+        return Status::BufferTooSmall;
+    }
+    if out_buf.is_null() && !answer.is_empty() {
+        return Status::NullPointer;
+    }
+    ptr::copy_nonoverlapping(answer.as_ptr(), out_buf, answer.len());
+    Status::Ok
+}
+
+/// Points `*out_ptr` / `*out_len` at the bundled puzzle input of a day; the data is static
+/// and must not be freed.
+///
+/// # Safety
+///
+/// `out_ptr` and `out_len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2020_input(
+    day: u32,
+    out_ptr: *mut *const u8,
+    out_len: *mut usize,
+) -> Aoc2020Status {
+    if out_ptr.is_null() || out_len.is_null() {
+        return Status::NullPointer;
+    }
+    let day = match day {
+        1..=25 => registry::find(registry::LATEST_YEAR, day as u8),
+        _ => None,
+    };
+    match day {
+        Some(day) => {
+            let s = (day.input)();
+            *out_ptr = s.as_ptr();
+            *out_len = s.len();
+            Status::Ok
+        }
+        None => Status::UnknownDay,
+    }
+}
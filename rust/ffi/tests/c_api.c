@@ -0,0 +1,66 @@
+/* exercises the C api, linked against the cdylib by tests/c_api.rs */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc2020.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                     \
+    do {                                                                \
+        if (!(cond)) {                                                  \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,     \
+                    __LINE__, #cond);                                   \
+            failures++;                                                 \
+        }                                                               \
+    } while (0)
+
+static int solves_to(uint32_t day, uint32_t part, const char *input,
+                     const char *expected) {
+    char buf[64];
+    size_t len = sizeof(buf);
+    Aoc2020Status status =
+        aoc2020_solve(day, part, (const uint8_t *)input, strlen(input),
+                      (uint8_t *)buf, &len);
+    return status == AOC2020_STATUS_OK && len == strlen(expected) &&
+           memcmp(buf, expected, len) == 0;
+}
+
+int main(void) {
+    const uint8_t *input;
+    size_t input_len, len;
+    char buf[64];
+
+    /* the example from the puzzle text */
+    CHECK(solves_to(1, 1, "1721\n979\n366\n299\n675\n1456\n", "514579"));
+    CHECK(solves_to(1, 2, "1721\n979\n366\n299\n675\n1456\n", "241861950"));
+    CHECK(solves_to(15, 1, "0,3,6\n", "436"));
+    CHECK(solves_to(15, 2, "0,3,6\n", "175594"));
+
+    /* the bundled input, and retrying after a too small buffer */
+    CHECK(aoc2020_input(8, &input, &input_len) == AOC2020_STATUS_OK);
+    len = 2;
+    CHECK(aoc2020_solve(8, 1, input, input_len, (uint8_t *)buf, &len) ==
+          AOC2020_STATUS_BUFFER_TOO_SMALL);
+    CHECK(len == 4);
+    CHECK(aoc2020_solve(8, 1, input, input_len, (uint8_t *)buf, &len) ==
+          AOC2020_STATUS_OK);
+    CHECK(len == 4 && memcmp(buf, "2058", 4) == 0);
+
+    /* bad arguments */
+    len = sizeof(buf);
+    CHECK(aoc2020_solve(26, 1, input, input_len, (uint8_t *)buf, &len) ==
+          AOC2020_STATUS_UNKNOWN_DAY);
+    CHECK(aoc2020_solve(8, 3, input, input_len, (uint8_t *)buf, &len) ==
+          AOC2020_STATUS_UNKNOWN_PART);
+    CHECK(aoc2020_solve(8, 1, input, input_len, (uint8_t *)buf, NULL) ==
+          AOC2020_STATUS_NULL_POINTER);
+    CHECK(aoc2020_solve(8, 1, NULL, 10, (uint8_t *)buf, &len) ==
+          AOC2020_STATUS_NULL_POINTER);
+    CHECK(aoc2020_input(0, &input, &input_len) == AOC2020_STATUS_UNKNOWN_DAY);
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures != 0;
+}
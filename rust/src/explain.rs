@@ -0,0 +1,31 @@
+use std::fmt::{self, Write};
+
+// a sink for the intermediate steps of a solver; solvers are generic over it so that with
+// the no-op `()` sink the steps compile away and the plain answers stay as fast as before
+pub trait Explain {
+    fn step(&mut self, args: fmt::Arguments<'_>);
+}
+
+impl Explain for () {
+    #[inline(always)]
+    fn step(&mut self, _: fmt::Arguments<'_>) {}
+}
+
+// records the steps as text, one `<step>: <details>` line each
+#[derive(Debug, Clone, Default)]
+pub struct Trace(String);
+
+impl Explain for Trace {
+    #[inline]
+    fn step(&mut self, args: fmt::Arguments<'_>) {
+        self.0.write_fmt(args).unwrap();
+        self.0.push('\n');
+    }
+}
+
+impl From<Trace> for String {
+    #[inline]
+    fn from(trace: Trace) -> Self {
+        trace.0
+    }
+}
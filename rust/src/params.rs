@@ -0,0 +1,61 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    UnknownKey(String),
+    InvalidValue(String, String),
+    OutOfRange(String, String, String), // along with the values that are accepted
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownKey(key) => write!(f, "unknown parameter: {}", key),
+            Self::InvalidValue(key, value) => write!(f, "invalid value for {}: {}", key, value),
+            Self::OutOfRange(key, value, accepted) => {
+                write!(
+                    f,
+                    "invalid value for {}: {} (must be {})",
+                    key, value, accepted
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+// overrides are stored as raw (key, value) pairs and applied on top of the defaults
+pub type Overrides = [(String, String)];
+
+pub trait Params: Default {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError>;
+    fn values(&self) -> Vec<(&'static str, String)>;
+
+    #[inline]
+    fn with_overrides(overrides: &Overrides) -> Result<Self, ParamError> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    fn set(&mut self, key: &str, _: &str) -> Result<(), ParamError> {
+        Err(ParamError::UnknownKey(key.into()))
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+#[inline]
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, ParamError> {
+    value
+        .parse()
+        .map_err(|_| ParamError::InvalidValue(key.into(), value.into()))
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+// how many iterations long loops run between check-ins, rare enough not to show in timings
+pub const CHUNK: usize = 1 << 16;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+// a flag that can be raised from another thread to stop a long-running solver
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// told how far along a long loop is every `CHUNK` iterations, and returns false to stop it;
+// like with `Explain`, the no-op `()` compiles away so the plain solvers stay as fast
+pub trait Progress {
+    fn report(&mut self, done: usize, total: usize) -> bool;
+}
+
+impl Progress for () {
+    #[inline(always)]
+    fn report(&mut self, _: usize, _: usize) -> bool {
+        true
+    }
+}
+
+impl Progress for Cancel {
+    #[inline]
+    fn report(&mut self, _: usize, _: usize) -> bool {
+        !self.is_cancelled()
+    }
+}
+
+impl<F: FnMut(usize, usize) -> bool> Progress for F {
+    #[inline]
+    fn report(&mut self, done: usize, total: usize) -> bool {
+        self(done, total)
+    }
+}
+
+// runs `f` over `0..total` a chunk at a time, checking in with `progress` after each one
+#[inline]
+pub fn chunked<P: Progress + ?Sized>(
+    total: usize,
+    progress: &mut P,
+    mut f: impl FnMut(Range<usize>),
+) -> Result<(), Cancelled> {
+    let mut done = 0;
+    while done < total {
+        let end = total.min(done + CHUNK);
+        f(done..end);
+        done = end;
+        if !progress.report(done, total) {
+            return Err(Cancelled);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_progress_chunked() {
+    let (mut sum, mut reports) = (0, Vec::new());
+    let mut progress = |done, total| {
+        reports.push((done, total));
+        true
+    };
+    let total = 2 * CHUNK + 1;
+    assert!(chunked(total, &mut progress, |r| sum += r.len()).is_ok());
+    assert_eq!(sum, total);
+    assert_eq!(
+        reports,
+        vec![(CHUNK, total), (2 * CHUNK, total), (total, total)]
+    );
+    assert!(chunked(0, &mut (), |_| unreachable!()).is_ok());
+}
+
+#[test]
+fn test_progress_cancel() {
+    let cancel = Cancel::new();
+    let mut chunks = 0;
+    let mut progress = cancel.clone();
+    assert!(chunked(10 * CHUNK, &mut progress, |_| chunks += 1).is_ok());
+    cancel.cancel();
+    assert_eq!(
+        chunked(10 * CHUNK, &mut progress, |_| chunks += 1),
+        Err(Cancelled)
+    );
+    assert_eq!(chunks, 11);
+}
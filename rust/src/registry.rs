@@ -0,0 +1,254 @@
+use std::time::{Duration, Instant};
+
+use crate::params::{Overrides, ParamError, Params};
+use crate::progress::{Cancelled, Progress};
+
+pub type Solver = fn(&[u8]) -> String;
+pub type BothSolver = fn(&[u8]) -> (String, String);
+pub type ParamSolver = fn(&[u8], &Overrides) -> Result<String, ParamError>;
+pub type ParamValues = Vec<(&'static str, String)>;
+pub type ProgressSolver =
+    fn(&[u8], &Overrides, &mut dyn Progress) -> Result<Result<String, Cancelled>, ParamError>;
+
+// an alternative algorithm for one of the parts, selectable by name
+#[derive(Copy, Clone)]
+pub struct Strategy {
+    pub name: &'static str,
+    pub part: u8,
+    pub solver: Solver,
+}
+
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub name: &'static str,
+    pub input: fn() -> &'static [u8],
+    pub part1: Solver,
+    pub part2: Solver,
+    pub solve_both: BothSolver, // parses the input once for both parts
+    pub part1_with: ParamSolver,
+    pub part2_with: ParamSolver,
+    pub params: fn(&Overrides) -> Result<ParamValues, ParamError>,
+    pub explain: Option<Solver>,
+    pub part2_progress: Option<ProgressSolver>, // for the days where part 2 runs long
+    pub strategies: &'static [Strategy],
+}
+
+impl Day {
+    #[inline]
+    pub fn solver(&self, part: u8) -> Option<Solver> {
+        match part {
+            1 => Some(self.part1),
+            2 => Some(self.part2),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn strategy(&self, part: u8, name: &str) -> Option<Solver> {
+        let mut strategies = self.strategies.iter();
+        let strategy = strategies.find(|st| st.part == part && st.name == name)?;
+        Some(strategy.solver)
+    }
+
+    #[inline]
+    pub fn solve_timed(&self, part: u8, s: &[u8]) -> Option<(String, Duration)> {
+        let solver = self.solver(part)?;
+        let start = Instant::now();
+        let answer = solver(s);
+        Some((answer, start.elapsed()))
+    }
+
+    #[inline]
+    pub fn solve_timed_with(
+        &self,
+        part: u8,
+        s: &[u8],
+        overrides: &Overrides,
+    ) -> Option<Result<(String, Duration), ParamError>> {
+        let solver = match part {
+            1 => self.part1_with,
+            2 => self.part2_with,
+            _ => return None,
+        };
+        let start = Instant::now();
+        Some(solver(s, overrides).map(|answer| (answer, start.elapsed())))
+    }
+}
+
+#[inline]
+fn without_params(overrides: &Overrides) -> Result<(), ParamError> {
+    <()>::with_overrides(overrides)
+}
+
+macro_rules! register {
+    ($($year:literal => $module:ident {
+        $($n:literal => $day:ident $(($($attr:ident),*))?),* $(,)?
+    }),* $(,)?) => {
+        pub static YEARS: &[u16] = &[$($year),*];
+        pub static DAYS: &[Day] = &[$($(
+            register!(@day $year, $n, $module, $day; $($($attr),*)?)
+        ),*),*];
+    };
+    // days opt into extras with attributes: `params` (a `Params` struct with `partN_with`
+    // solvers), `explain` (an `explain` function tracing the solution steps), `strategies`
+    // (a `STRATEGIES` list of alternative algorithms, the first one per part being the
+    // default) and `progress` (a `part2_with_progress` solver that reports how far along it
+    // is and can be cancelled)
+    (@has params; params $(, $rest:ident)*; $yes:expr; $no:expr) => { $yes };
+    (@has explain; explain $(, $rest:ident)*; $yes:expr; $no:expr) => { $yes };
+    (@has strategies; strategies $(, $rest:ident)*; $yes:expr; $no:expr) => { $yes };
+    (@has progress; progress $(, $rest:ident)*; $yes:expr; $no:expr) => { $yes };
+    (@has $flag:ident; $attr:ident $(, $rest:ident)*; $yes:expr; $no:expr) => {
+        register!(@has $flag; $($rest),*; $yes; $no)
+    };
+    (@has $flag:ident; ; $yes:expr; $no:expr) => { $no };
+    (@day $year:expr, $n:expr, $module:ident, $day:ident; $($attr:ident),*) => {
+        Day {
+            year: $year,
+            day: $n,
+            name: stringify!($day),
+            input: crate::$module::$day::input,
+            part1: |s| crate::$module::$day::part1(s).to_string(),
+            part2: |s| crate::$module::$day::part2(s).to_string(),
+            solve_both: |s| {
+                let (answer1, answer2) = crate::$module::$day::solve_both(s);
+                (answer1.to_string(), answer2.to_string())
+            },
+            part1_with: register!(@has params; $($attr),*;
+                |s, o| {
+                    let p = crate::$module::$day::Params::with_overrides(o)?;
+                    Ok(crate::$module::$day::part1_with(s, &p).to_string())
+                };
+                |s, o| without_params(o).map(|_| crate::$module::$day::part1(s).to_string())
+            ),
+            part2_with: register!(@has params; $($attr),*;
+                |s, o| {
+                    let p = crate::$module::$day::Params::with_overrides(o)?;
+                    Ok(crate::$module::$day::part2_with(s, &p).to_string())
+                };
+                |s, o| without_params(o).map(|_| crate::$module::$day::part2(s).to_string())
+            ),
+            params: register!(@has params; $($attr),*;
+                |o| crate::$module::$day::Params::with_overrides(o).map(|p| p.values());
+                |o| without_params(o).map(|_| Vec::new())
+            ),
+            explain: register!(@has explain; $($attr),*;
+                Some(crate::$module::$day::explain);
+                None
+            ),
+            part2_progress: register!(@has progress; $($attr),*;
+                Some(register!(@has params; $($attr),*;
+                    |s, o, p| {
+                        let params = crate::$module::$day::Params::with_overrides(o)?;
+                        let answer = crate::$module::$day::part2_with_progress(s, &params, p);
+                        Ok(answer.map(|answer| answer.to_string()))
+                    };
+                    |s, o, p| without_params(o).map(|_| {
+                        crate::$module::$day::part2_with_progress(s, p).map(|a| a.to_string())
+                    })
+                ));
+                None
+            ),
+            strategies: register!(@has strategies; $($attr),*;
+                crate::$module::$day::STRATEGIES;
+                &[]
+            ),
+        }
+    };
+}
+
+register! {
+    2020 => year2020 {
+        1 => day01(params, strategies),
+        2 => day02(strategies),
+        3 => day03(strategies),
+        4 => day04,
+        5 => day05,
+        6 => day06,
+        7 => day07(params),
+        8 => day08(explain),
+        9 => day09(params),
+        10 => day10,
+        11 => day11(params, strategies),
+        12 => day12,
+        13 => day13(explain),
+        14 => day14,
+        15 => day15(params, progress),
+        16 => day16(explain),
+        17 => day17(params),
+        18 => day18,
+        19 => day19,
+        20 => day20(explain),
+        21 => day21(explain),
+        22 => day22,
+        23 => day23(params, progress),
+        24 => day24(params),
+        25 => day25(strategies),
+    },
+}
+
+pub const LATEST_YEAR: u16 = 2020;
+
+#[inline]
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.day == day)
+}
+
+#[inline]
+pub fn find_by_name(year: u16, name: &str) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.year == year && d.name == name)
+}
+
+#[inline]
+pub fn days(year: u16) -> impl Iterator<Item = &'static Day> {
+    DAYS.iter().filter(move |d| d.year == year)
+}
+
+#[test]
+fn test_registry_dispatch() {
+    let day = find(2020, 8).unwrap();
+    assert_eq!(day.name, "day08");
+    assert_eq!((day.part1)((day.input)()), "2058");
+    assert_eq!(
+        (day.solve_both)((day.input)()),
+        ("2058".into(), "1000".into())
+    );
+    assert!(day.solver(3).is_none());
+    assert!(find(2020, 26).is_none());
+    assert!(find(2019, 1).is_none());
+    assert_eq!(days(2020).count(), 25);
+    let day = find(2020, 11).unwrap();
+    assert_eq!(day.strategy(2, "conv7").unwrap()((day.input)()), "2042");
+    assert!(day.strategy(1, "conv7").is_none());
+    assert!(day.part2_progress.is_none());
+    let day = find(2020, 23).unwrap();
+    let mut stop = |_, _| false;
+    let answer = day.part2_progress.unwrap()((day.input)(), &[], &mut stop);
+    assert_eq!(answer, Ok(Err(Cancelled)));
+}
+
+#[test]
+fn test_registry_params() {
+    let day = find_by_name(2020, "day23").unwrap();
+    let overrides = [("moves".to_owned(), "10".to_owned())];
+    let (answer, _) = day
+        .solve_timed_with(1, b"389125467", &overrides)
+        .unwrap()
+        .unwrap();
+    assert_eq!(answer, "92658374");
+    let overrides = [("speed".to_owned(), "1".to_owned())];
+    assert!((day.params)(&overrides).is_err());
+    assert!((find(2020, 1).unwrap().params)(&overrides).is_err());
+    assert!((find(2020, 2).unwrap().params)(&overrides).is_err());
+    let day = find(2020, 15).unwrap();
+    let overrides = [("turns2".to_owned(), "10".to_owned())];
+    let (answer, _) = day
+        .solve_timed_with(2, b"0,3,6\n", &overrides)
+        .unwrap()
+        .unwrap();
+    assert_eq!(answer, "0");
+    let answer = day.part2_progress.unwrap()(b"0,3,6\n", &overrides, &mut ());
+    assert_eq!(answer, Ok(Ok("0".into())));
+}
@@ -0,0 +1,284 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use bstr::ByteSlice;
+
+use aoc2020::registry::{Day, Solver};
+
+use crate::reference;
+use crate::SOLVER_STACK_SIZE;
+
+pub const DEFAULT_SEED: u64 = 2020;
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+const MAX_MUTATIONS: usize = 3;
+const MINIMIZE_BUDGET: usize = 1000; // max number of solver runs spent on shrinking one failure
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// xorshift64*, good enough for picking mutations and fully reproducible from the seed
+pub struct Rng(u64);
+
+impl Rng {
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    #[inline]
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    #[inline]
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn mutate(rng: &mut Rng, s: &[u8]) -> Vec<u8> {
+    let mut out = s.to_vec();
+    for _ in 0..1 + rng.below(MAX_MUTATIONS) {
+        if out.is_empty() {
+            break;
+        }
+        let len = out.len();
+        match rng.below(3) {
+            0 => {
+                // half of the time, pick a byte that's already in the input so it's more likely to parse
+                let i = rng.below(len);
+                out[i] = if rng.below(2) == 0 {
+                    out[rng.below(len)]
+                } else {
+                    rng.next() as u8
+                };
+            }
+            1 => out.truncate(rng.below(len)),
+            _ => {
+                let start = rng.below(len);
+                let start = out[..start].rfind_byte(b'\n').map_or(0, |i| i + 1);
+                let end = out[start..].find_byte(b'\n').map_or(len, |i| start + i + 1);
+                let line = out[start..end].to_vec();
+                out.splice(end..end, line);
+            }
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Answer(String),
+    Panic { location: String, message: String },
+    Hang,
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+fn record_panic(location: Option<&Location<'_>>, payload: &(dyn Any + Send)) {
+    let location = location.map_or_else(String::new, |loc| loc.to_string());
+    let message = match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => String::new(),
+    };
+    LAST_PANIC.with(|p| *p.borrow_mut() = Some((location, message)));
+}
+
+fn run(solver: Solver, s: &[u8]) -> Outcome {
+    let s = s.to_vec();
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || {
+            let outcome = match panic::catch_unwind(AssertUnwindSafe(|| solver(&s))) {
+                Ok(answer) => Outcome::Answer(answer),
+                Err(_) => {
+                    let (location, message) = LAST_PANIC
+                        .with(|p| p.borrow_mut().take())
+                        .unwrap_or_default();
+                    Outcome::Panic { location, message }
+                }
+            };
+            let _ = tx.send(outcome);
+        })
+        .expect("failed to spawn a solver thread");
+    // there's no way to kill a thread, so a hanging solver is left running in the background
+    rx.recv_timeout(TIMEOUT).unwrap_or(Outcome::Hang)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Failure {
+    Panic { location: String, message: String },
+    Mismatch { answer: String, expected: String },
+    Hang,
+}
+
+impl Failure {
+    // failures with the same signature are assumed to be the same bug
+    fn signature(&self) -> &str {
+        match self {
+            Self::Panic { location, .. } => location,
+            Self::Mismatch { .. } => "mismatch",
+            Self::Hang => "hang",
+        }
+    }
+}
+
+fn check(day: &Day, part: u8, s: &[u8]) -> Option<Failure> {
+    let answer = match run(day.solver(part)?, s) {
+        Outcome::Answer(answer) => answer,
+        Outcome::Panic { location, message } => return Some(Failure::Panic { location, message }),
+        Outcome::Hang => return Some(Failure::Hang),
+    };
+    // the input is considered to be still valid if the naive solution accepts it
+    match reference::find(day.year, day.day, part)?(s) {
+        Some(expected) if expected != answer => Some(Failure::Mismatch { answer, expected }),
+        _ => None,
+    }
+}
+
+fn minimize(mut s: Vec<u8>, mut still_fails: impl FnMut(&[u8]) -> bool) -> Vec<u8> {
+    // greedily cut out chunks of the input, halving the chunk size until nothing can be removed
+    let mut budget = MINIMIZE_BUDGET;
+    let mut chunk = s.len() / 2;
+    while chunk != 0 {
+        let mut i = 0;
+        while i < s.len() {
+            if budget == 0 {
+                return s;
+            }
+            budget -= 1;
+            let end = (i + chunk).min(s.len());
+            let candidate = [&s[..i], &s[end..]].concat();
+            if still_fails(&candidate) {
+                s = candidate;
+            } else {
+                i += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+    s
+}
+
+fn report(day: &Day, part: u8, failure: &Failure, reproducer: &[u8], iteration: usize) {
+    match failure {
+        Failure::Panic { location, message } => {
+            println!(
+                "{} part{}: panicked at {}: {}",
+                day.name, part, location, message
+            )
+        }
+        Failure::Mismatch { answer, expected } => println!(
+            "{} part{}: answered {}, the naive solution answered {}",
+            day.name, part, answer, expected
+        ),
+        Failure::Hang => println!("{} part{}: no answer after {:?}", day.name, part, TIMEOUT),
+    }
+    println!(
+        "  reproducer ({} bytes, iteration {}): {:?}",
+        reproducer.len(),
+        iteration,
+        reproducer.as_bstr()
+    );
+}
+
+fn fuzz_day(day: &Day, seed: u64, iterations: usize) -> usize {
+    let original = (day.input)();
+    let mut rng = Rng::new(seed ^ ((day.year as u64) << 8 | day.day as u64));
+    let mut seen = BTreeSet::new();
+    for iteration in 0..=iterations {
+        // iteration 0 is the unmodified input
+        let s = if iteration == 0 {
+            original.to_vec()
+        } else {
+            mutate(&mut rng, original)
+        };
+        for part in 1..=2 {
+            let failure = match check(day, part, &s) {
+                Some(failure) => failure,
+                None => continue,
+            };
+            if !seen.insert((part, failure.signature().to_owned())) {
+                continue;
+            }
+            let reproducer = match failure {
+                Failure::Hang => s.clone(), // too slow to shrink
+                _ => minimize(
+                    s.clone(),
+                    |s| matches!(check(day, part, s), Some(f) if f.signature() == failure.signature()),
+                ),
+            };
+            report(day, part, &failure, &reproducer, iteration);
+        }
+    }
+    seen.len()
+}
+
+pub fn fuzz(days: &[&'static Day], seed: u64, iterations: usize) -> usize {
+    if !cfg!(feature = "checked") {
+        eprintln!(
+            "note: built without the `checked` feature, some out-of-bounds reads may go unnoticed"
+        );
+    }
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        record_panic(info.location(), info.payload())
+    }));
+    let mut failures = 0;
+    for day in days {
+        let n = fuzz_day(day, seed, iterations);
+        // without a naive solution, a wrong answer goes unnoticed as long as nothing panics
+        let unchecked: Vec<_> = (1..=2)
+            .filter(|&part| reference::find(day.year, day.day, part).is_none())
+            .map(|part| format!("part{}", part))
+            .collect();
+        let note = match unchecked.len() {
+            0 => String::new(),
+            2 => " (panics and hangs only, no naive solution)".to_owned(),
+            _ => format!(" ({} panics and hangs only)", unchecked.join(", ")),
+        };
+        eprintln!(
+            "{}: {} inputs, {} distinct failures{}",
+            day.name,
+            iterations + 1,
+            n,
+            note
+        );
+        failures += n;
+    }
+    panic::set_hook(hook);
+    failures
+}
+
+#[test]
+fn test_fuzz_mutate() {
+    let s = b"1721\n979\n366\n299\n675\n1456\n\n";
+    let (mut a, mut b) = (Rng::new(1), Rng::new(1));
+    for _ in 0..100 {
+        assert_eq!(mutate(&mut a, s), mutate(&mut b, s));
+    }
+}
+
+#[test]
+fn test_fuzz_minimize() {
+    let s = b"abc\ndef\nxyz\nghi\n".to_vec();
+    assert_eq!(minimize(s.clone(), |s| s.contains(&b'y')), b"y");
+    assert_eq!(
+        minimize(s, |s| s.find(b"de").is_some() && s.ends_with(b"\n")),
+        b"de\n"
+    );
+}
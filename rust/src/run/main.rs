@@ -0,0 +1,304 @@
+mod fuzz;
+mod mem;
+mod metamorphic;
+mod reference;
+mod repl;
+mod serve;
+mod watch;
+
+use std::env;
+use std::fs;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc2020::progress::{Cancel, Cancelled};
+use aoc2020::registry::{self, Day, ProgressSolver, Solver};
+
+// some of the solvers keep multi-megabyte boards on the stack
+const SOLVER_STACK_SIZE: usize = 64 << 20;
+
+#[global_allocator]
+static ALLOCATOR: mem::CountingAlloc = mem::CountingAlloc;
+
+const USAGE: &str = "\
+usage: run [--year YYYY] [--strategy DAY=NAME]... [--param DAY.KEY=VALUE]...
+           [--progress] [--timeout SECS]
+       run [--year YYYY] --mem
+       run [--year YYYY] explain DAY [FILE]
+       run [--year YYYY] strategies
+       run [--year YYYY] serve [--port N]
+       run [--year YYYY] repl
+       run [--year YYYY] [--param DAY.KEY=VALUE]... watch DAY FILE
+       run [--year YYYY] fuzz [--seed N] [--iters N] [DAY...]
+       run [--year YYYY] metamorphic [--seed N] [--iters N]";
+
+type Selected = [(&'static Day, String)];
+type Params = [(&'static Day, (String, String))];
+type Overrides = Vec<(String, String)>;
+
+fn is_selected(day: &Day, selected: &Selected) -> bool {
+    selected
+        .iter()
+        .any(|(d, _)| d.year == day.year && d.day == day.day)
+}
+
+fn select(day: &Day, part: u8, selected: &Selected) -> Solver {
+    let mut names = selected
+        .iter()
+        .filter(|(d, _)| d.year == day.year && d.day == day.day);
+    let solver = names.find_map(|(_, name)| day.strategy(part, name));
+    solver.or_else(|| day.solver(part)).unwrap()
+}
+
+// how the long-running parts are run: with a progress line on stderr and/or a time limit
+#[derive(Debug, Default)]
+struct Monitor {
+    progress: bool,
+    timeout: Option<Duration>,
+}
+
+impl Monitor {
+    fn is_active(&self) -> bool {
+        self.progress || self.timeout.is_some()
+    }
+
+    fn run(&self, day: &Day, solver: ProgressSolver, s: &[u8], overrides: &Overrides) -> String {
+        let cancel = Cancel::new();
+        if let Some(timeout) = self.timeout {
+            let cancel = cancel.clone();
+            thread::spawn(move || {
+                thread::sleep(timeout);
+                cancel.cancel();
+            });
+        }
+        let start = Instant::now();
+        let mut shown = None;
+        let mut report = |done: usize, total: usize| {
+            let percent = done * 100 / total.max(1);
+            if self.progress && shown != Some(percent) {
+                eprint!("\r{} part2: {:>3}%", day.name, percent);
+                shown = Some(percent);
+            }
+            !cancel.is_cancelled()
+        };
+        let answer = solver(s, overrides, &mut report).expect(CHECKED);
+        if shown.is_some() {
+            eprint!("\r\x1b[K"); // clear the progress line
+        }
+        match answer {
+            Ok(answer) => answer,
+            Err(Cancelled) => format!("cancelled after {:.1?}", start.elapsed()),
+        }
+    }
+}
+
+const CHECKED: &str = "parameters are checked upfront";
+
+fn parse_param(year: u16, arg: &str) -> Option<(&'static Day, (String, String))> {
+    // e.g. `day23.moves=1000` or `23.moves=1000`
+    let (key, value) = arg.split_once('=')?;
+    let (day, key) = key.split_once('.')?;
+    Some((parse_day(year, day)?, (key.to_owned(), value.to_owned())))
+}
+
+fn overrides(day: &Day, params: &Params) -> Overrides {
+    let params = params
+        .iter()
+        .filter(|(d, _)| d.year == day.year && d.day == day.day);
+    params.map(|(_, kv)| kv.clone()).collect()
+}
+
+fn run_all(year: u16, selected: &Selected, params: &Params, monitor: &Monitor) {
+    for day in registry::days(year) {
+        let s = (day.input)();
+        let overrides = overrides(day, params);
+        // the input is parsed once for both parts, unless a strategy was picked for a part,
+        // the parameters were changed or part 2 has to be monitored
+        let (answer1, answer2) = match day.part2_progress {
+            Some(solver) if monitor.is_active() && !is_selected(day, selected) => (
+                (day.part1_with)(s, &overrides).expect(CHECKED),
+                monitor.run(day, solver, s, &overrides),
+            ),
+            _ if is_selected(day, selected) => {
+                (select(day, 1, selected)(s), select(day, 2, selected)(s))
+            }
+            _ if !overrides.is_empty() => (
+                (day.part1_with)(s, &overrides).expect(CHECKED),
+                (day.part2_with)(s, &overrides).expect(CHECKED),
+            ),
+            _ => (day.solve_both)(s),
+        };
+        println!("{}: part1 = {}, part2 = {}", day.name, answer1, answer2);
+    }
+}
+
+fn compare_strategies(year: u16) {
+    for day in registry::days(year) {
+        let s = (day.input)();
+        for strategy in day.strategies {
+            let start = Instant::now();
+            let answer = (strategy.solver)(s);
+            let elapsed = start.elapsed();
+            println!(
+                "{} part{} {}: {} ({:?})",
+                day.name, strategy.part, strategy.name, answer, elapsed
+            );
+        }
+    }
+}
+
+fn parse_day(year: u16, arg: &str) -> Option<&'static Day> {
+    // either the day number or its name, e.g. `8` or `day08`
+    registry::find_by_name(year, arg).or_else(|| {
+        let day = arg.trim_start_matches("day").parse().ok()?;
+        registry::find(year, day)
+    })
+}
+
+fn parse_strategy(year: u16, arg: &str) -> Option<(&'static Day, String)> {
+    let (day, name) = arg.split_once('=')?;
+    let day = parse_day(year, day)?;
+    let known = day.strategies.iter().any(|st| st.name == name);
+    Some((day, name.to_owned())).filter(|_| known)
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
+
+fn main() {
+    let mut args = env::args().skip(1).collect::<Vec<_>>();
+    let mut year = registry::LATEST_YEAR;
+    if let Some(i) = args.iter().position(|arg| arg == "--year") {
+        year = match args.get(i + 1).and_then(|y| y.parse().ok()) {
+            Some(year) if registry::YEARS.contains(&year) => year,
+            _ => usage(),
+        };
+        args.drain(i..i + 2);
+    }
+    let mut selected = Vec::new();
+    while let Some(i) = args.iter().position(|arg| arg == "--strategy") {
+        let strategy = args.get(i + 1).and_then(|arg| parse_strategy(year, arg));
+        selected.push(strategy.unwrap_or_else(|| usage()));
+        args.drain(i..i + 2);
+    }
+    let mut params = Vec::new();
+    while let Some(i) = args.iter().position(|arg| arg == "--param") {
+        let param = args.get(i + 1).and_then(|arg| parse_param(year, arg));
+        params.push(param.unwrap_or_else(|| usage()));
+        args.drain(i..i + 2);
+    }
+    for &(day, _) in &params {
+        if let Err(err) = (day.params)(&overrides(day, &params)) {
+            eprintln!("error: {}: {}", day.name, err);
+            process::exit(1);
+        }
+    }
+    let mut monitor = Monitor::default();
+    if let Some(i) = args.iter().position(|arg| arg == "--progress") {
+        monitor.progress = true;
+        args.remove(i);
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--timeout") {
+        let secs = args.get(i + 1).and_then(|secs| secs.parse().ok());
+        let secs: f64 = secs.filter(|&secs| secs >= 0.).unwrap_or_else(|| usage());
+        monitor.timeout = Some(Duration::from_secs_f64(secs));
+        args.drain(i..i + 2);
+    }
+    match args.first().map(String::as_str) {
+        None => run_all(year, &selected, &params, &monitor),
+        Some("strategies") if args.len() == 1 => compare_strategies(year),
+        Some("--mem") if args.len() == 1 => mem::report(registry::days(year)),
+        Some("explain") => {
+            let day = args.get(1).and_then(|day| parse_day(year, day));
+            let day = day.unwrap_or_else(|| usage());
+            let input = match args.get(2) {
+                Some(path) => fs::read(path).unwrap_or_else(|err| {
+                    eprintln!("error: {}: {}", path, err);
+                    process::exit(1);
+                }),
+                None => (day.input)().to_vec(),
+            };
+            match day.explain {
+                Some(explain) => print!("{}", explain(&input)),
+                None => {
+                    eprintln!("error: {} has nothing to explain", day.name);
+                    process::exit(1);
+                }
+            }
+        }
+        Some("serve") => {
+            let mut port = serve::DEFAULT_PORT;
+            let mut args = args[1..].iter();
+            while let Some(arg) = args.next() {
+                match (arg.as_str(), args.next()) {
+                    ("--port", Some(n)) => port = n.parse().unwrap_or_else(|_| usage()),
+                    _ => usage(),
+                }
+            }
+            if let Err(err) = serve::serve(port, year) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Some("watch") if args.len() == 3 => {
+            let day = parse_day(year, &args[1]).unwrap_or_else(|| usage());
+            if let Err(err) = watch::watch(day, &args[2], overrides(day, &params)) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Some("repl") => {
+            if let Err(err) = repl::repl(year) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Some("fuzz") => {
+            let (mut seed, mut iterations) = (fuzz::DEFAULT_SEED, fuzz::DEFAULT_ITERATIONS);
+            let mut days = Vec::new();
+            let mut args = args[1..].iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--seed" => {
+                        seed = args
+                            .next()
+                            .and_then(|n| n.parse().ok())
+                            .unwrap_or_else(|| usage())
+                    }
+                    "--iters" => {
+                        iterations = args
+                            .next()
+                            .and_then(|n| n.parse().ok())
+                            .unwrap_or_else(|| usage())
+                    }
+                    day => days.push(parse_day(year, day).unwrap_or_else(|| usage())),
+                }
+            }
+            if days.is_empty() {
+                days = registry::days(year).collect();
+            }
+            if fuzz::fuzz(&days, seed, iterations) != 0 {
+                process::exit(1);
+            }
+        }
+        Some("metamorphic") => {
+            let (mut seed, mut iterations) = (fuzz::DEFAULT_SEED, metamorphic::DEFAULT_ITERATIONS);
+            let mut args = args[1..].iter();
+            while let Some(arg) = args.next() {
+                let n = args.next().and_then(|n| n.parse().ok());
+                match (arg.as_str(), n) {
+                    ("--seed", Some(n)) => seed = n,
+                    ("--iters", Some(n)) => iterations = n as usize,
+                    _ => usage(),
+                }
+            }
+            if metamorphic::metamorphic(year, seed, iterations) != 0 {
+                process::exit(1);
+            }
+        }
+        Some(_) => usage(),
+    }
+}
@@ -0,0 +1,180 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed};
+use std::thread;
+
+use aoc2020::registry::Day;
+
+use crate::SOLVER_STACK_SIZE;
+
+// global allocator that keeps track of the number of allocations and of the peak heap usage;
+// the counters are shared across threads, so measurements are only exact when run serially
+pub struct CountingAlloc;
+
+// nothing is counted until the first measurement, so the other commands only pay for a load per
+// allocation; memory allocated before that may be freed while counting, so the heap total can go
+// below zero and only its changes mean anything
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+#[inline]
+fn counting() -> bool {
+    COUNTING.load(Relaxed)
+}
+
+#[inline]
+fn grow(size: usize) {
+    let current = CURRENT.fetch_add(size as isize, Relaxed) + size as isize;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+#[inline]
+fn shrink(size: usize) {
+    CURRENT.fetch_sub(size as isize, Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && counting() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    #[inline]
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && counting() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if counting() {
+            shrink(layout.size());
+        }
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && counting() {
+            ALLOCATIONS.fetch_add(1, Relaxed);
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct MemUsage {
+    pub allocations: usize,
+    pub peak_heap: usize,
+    pub peak_stack: usize,
+}
+
+const STACK_PAINT: u8 = 0xa5;
+const STACK_MARGIN: usize = 1 << 20; // leave room for thread setup and the guard page
+const STACK_GAP: usize = 1 << 10; // leave the frames right below the current one alone
+
+#[inline(never)]
+fn stack_top() -> usize {
+    let marker = 0u8;
+    &marker as *const u8 as usize
+}
+
+#[inline(never)]
+fn measure_on_thread(f: impl FnOnce()) -> MemUsage {
+    // paint the unused part of this thread's stack with a known byte pattern; after running
+    // `f`, the lowest byte that no longer matches the pattern marks the stack high-water mark
+    let top = stack_top();
+    let len = SOLVER_STACK_SIZE - STACK_MARGIN;
+    let bottom = top - len;
+    unsafe { ptr::write_bytes(bottom as *mut u8, STACK_PAINT, len - STACK_GAP) };
+
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let base = CURRENT.load(Relaxed);
+    PEAK.store(base, Relaxed);
+
+    f();
+
+    let allocations = ALLOCATIONS.load(Relaxed) - allocations;
+    let peak_heap = (PEAK.load(Relaxed) - base).max(0) as usize;
+    let mut lowest = bottom;
+    while lowest < top - STACK_GAP
+        && unsafe { ptr::read_volatile(lowest as *const u8) } == STACK_PAINT
+    {
+        lowest += 1;
+    }
+    MemUsage {
+        allocations,
+        peak_heap,
+        peak_stack: top - lowest,
+    }
+}
+
+pub fn measure(day: &Day, part: u8, s: &[u8]) -> Option<MemUsage> {
+    // returns `None` if the solver panicked, which can only be told apart with unwinding (the
+    // `fuzz` profile); with the release profile a panic aborts the whole report
+    let solver = day.solver(part)?;
+    COUNTING.store(true, Relaxed);
+    let s = s.to_vec();
+    thread::Builder::new()
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || measure_on_thread(|| drop(solver(&s))))
+        .expect("failed to spawn a solver thread")
+        .join()
+        .ok()
+}
+
+fn format_bytes(n: usize) -> String {
+    match n {
+        n if n >= 10 << 20 => format!("{} MB", n >> 20),
+        n if n >= 10 << 10 => format!("{} KB", n >> 10),
+        n => format!("{} B", n),
+    }
+}
+
+pub fn report(days: impl Iterator<Item = &'static Day>) {
+    // note: the numbers include formatting the answer into a string
+    println!(
+        "{:<8}{:>6}{:>10}{:>12}{:>12}",
+        "day", "part", "allocs", "peak heap", "peak stack"
+    );
+    for day in days {
+        let s = (day.input)();
+        for part in 1..=2 {
+            match measure(day, part, s) {
+                Some(usage) => println!(
+                    "{:<8}{:>6}{:>10}{:>12}{:>12}",
+                    day.name,
+                    part,
+                    usage.allocations,
+                    format_bytes(usage.peak_heap),
+                    format_bytes(usage.peak_stack)
+                ),
+                None => println!("{:<8}{:>6}{:>34}", day.name, part, "panicked"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mem_measure() {
+    // other tests may allocate concurrently, so only check the lower bounds
+    let day = aoc2020::registry::find(2020, 17).unwrap();
+    let usage = measure(day, 1, (day.input)()).unwrap();
+    assert!(usage.allocations >= 1);
+    assert!(usage.peak_stack >= 64 << 10);
+    assert!(measure(day, 3, b"").is_none());
+}
@@ -0,0 +1,255 @@
+use std::collections::BTreeSet;
+
+use rustc_hash::FxHashMap;
+
+use aoc2020::registry::{self, Day};
+
+use crate::fuzz::Rng;
+
+pub const DEFAULT_ITERATIONS: usize = 10;
+
+// an input rewrite that shouldn't change the answers to the given parts
+struct Transform {
+    day: u8,
+    name: &'static str,
+    parts: &'static [u8],
+    apply: fn(&mut Rng, &[u8]) -> Vec<u8>,
+}
+
+static TRANSFORMS: &[Transform] = &[
+    Transform {
+        day: 1,
+        name: "shuffle-lines",
+        parts: &[1, 2],
+        apply: shuffle_lines,
+    },
+    Transform {
+        day: 7,
+        name: "rename-colours",
+        parts: &[1, 2],
+        apply: rename_colours,
+    },
+    Transform {
+        day: 10,
+        name: "shuffle-lines",
+        parts: &[1, 2],
+        apply: shuffle_lines,
+    },
+    Transform {
+        day: 20,
+        name: "shuffle-tiles",
+        parts: &[1, 2],
+        apply: shuffle_tiles,
+    },
+    Transform {
+        day: 20,
+        name: "orient-tiles",
+        parts: &[1, 2],
+        apply: orient_tiles,
+    },
+    Transform {
+        day: 21,
+        name: "rename-ingredients",
+        parts: &[1],
+        apply: rename_ingredients,
+    },
+];
+
+fn shuffle<T>(rng: &mut Rng, v: &mut [T]) {
+    for i in (1..v.len()).rev() {
+        v.swap(i, rng.below(i + 1));
+    }
+}
+
+fn lines(s: &[u8]) -> Vec<&[u8]> {
+    s.split(|&c| c == b'\n').filter(|l| !l.is_empty()).collect()
+}
+
+fn join(lines: impl IntoIterator<Item = impl AsRef<[u8]>>, sep: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for line in lines {
+        out.extend_from_slice(line.as_ref());
+        out.extend_from_slice(sep);
+    }
+    out
+}
+
+fn shuffle_lines(rng: &mut Rng, s: &[u8]) -> Vec<u8> {
+    let mut lines = lines(s);
+    shuffle(rng, &mut lines);
+    join(lines, b"\n")
+}
+
+fn rename_colours(rng: &mut Rng, s: &[u8]) -> Vec<u8> {
+    // swaps the colours around (all but the one the puzzle asks about), so they keep looking
+    // like the real ones but every rule now talks about different bags
+    let lines: Vec<Vec<&str>> = lines(s)
+        .into_iter()
+        .map(|line| std::str::from_utf8(line).unwrap().split(' ').collect())
+        .collect();
+    let colour = |words: &[&str], i: usize| format!("{} {}", words[i], words[i + 1]);
+    let mut names: Vec<_> = lines.iter().map(|words| colour(words, 0)).collect();
+    names.retain(|name| name != "shiny gold");
+    let mut renamed = names.clone();
+    shuffle(rng, &mut renamed);
+    let map: FxHashMap<_, _> = names.into_iter().zip(renamed).collect();
+    let rename = |name: String| map.get(&name).cloned().unwrap_or(name);
+    let lines = lines.iter().map(|words| {
+        let mut out = vec![rename(colour(words, 0))];
+        let mut i = 2;
+        while i < words.len() {
+            if words[i].bytes().all(|c| c.is_ascii_digit()) {
+                out.push(words[i].to_owned());
+                out.push(rename(colour(words, i + 1)));
+                i += 3;
+            } else {
+                out.push(words[i].to_owned());
+                i += 1;
+            }
+        }
+        out.join(" ")
+    });
+    join(lines, b"\n")
+}
+
+fn tiles(s: &[u8]) -> Vec<Vec<&[u8]>> {
+    // each tile is a header line followed by its rows
+    let mut tiles: Vec<Vec<&[u8]>> = Vec::new();
+    for line in lines(s) {
+        match tiles.last_mut() {
+            Some(tile) if !line.starts_with(b"Tile") => tile.push(line),
+            _ => tiles.push(vec![line]),
+        }
+    }
+    tiles
+}
+
+fn shuffle_tiles(rng: &mut Rng, s: &[u8]) -> Vec<u8> {
+    let mut tiles = tiles(s);
+    shuffle(rng, &mut tiles);
+    join(tiles.into_iter().map(|tile| join(tile, b"\n")), b"\n")
+}
+
+fn orient(rows: &[&[u8]], k: usize) -> Vec<Vec<u8>> {
+    // one of the 8 rotations and reflections of a square
+    let n = rows.len();
+    let mut out = vec![vec![0; n]; n];
+    for (y, row) in out.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            let (y, x) = match k % 4 {
+                0 => (y, x),
+                1 => (n - 1 - x, y),
+                2 => (n - 1 - y, n - 1 - x),
+                _ => (x, n - 1 - y),
+            };
+            let x = if k >= 4 { n - 1 - x } else { x };
+            *cell = rows[y][x];
+        }
+    }
+    out
+}
+
+fn orient_tiles(rng: &mut Rng, s: &[u8]) -> Vec<u8> {
+    let tiles = tiles(s).into_iter().map(|tile| {
+        let rows = orient(&tile[1..], rng.below(8));
+        let header = [tile[0]];
+        let lines = header.iter().copied().chain(rows.iter().map(Vec::as_slice));
+        join(lines, b"\n")
+    });
+    join(tiles, b"\n")
+}
+
+fn rename_ingredients(rng: &mut Rng, s: &[u8]) -> Vec<u8> {
+    // gives every ingredient a fresh random name; the allergens are left alone
+    let mut map = FxHashMap::default();
+    let mut taken = BTreeSet::new();
+    let lines = lines(s).into_iter().map(|line| {
+        let split = line.iter().position(|&c| c == b'(').unwrap_or(line.len());
+        let (ingredients, allergens) = line.split_at(split);
+        let mut out = Vec::new();
+        for name in ingredients.split(|&c| c == b' ').filter(|w| !w.is_empty()) {
+            let name = map.entry(name).or_insert_with(|| loop {
+                let len = 2 + rng.below(7);
+                let name: Vec<u8> = (0..len).map(|_| b'a' + rng.below(26) as u8).collect();
+                if taken.insert(name.clone()) {
+                    break name;
+                }
+            });
+            out.extend_from_slice(name);
+            out.push(b' ');
+        }
+        out.extend_from_slice(allergens);
+        out
+    });
+    join(lines.collect::<Vec<_>>(), b"\n")
+}
+
+fn check(day: &Day, transform: &Transform, rng: &mut Rng) -> Result<(), String> {
+    let original = (day.input)();
+    let s = (transform.apply)(rng, original);
+    for &part in transform.parts {
+        let solver = day.solver(part).unwrap();
+        let (expected, answer) = (solver(original), solver(&s));
+        if answer != expected {
+            return Err(format!(
+                "part{}: expected {}, got {}",
+                part, expected, answer
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn metamorphic(year: u16, seed: u64, iterations: usize) -> usize {
+    let mut failures = 0;
+    for transform in TRANSFORMS {
+        let day = match registry::find(year, transform.day) {
+            Some(day) => day,
+            None => continue,
+        };
+        let mut rng = Rng::new(seed ^ transform.day as u64);
+        let mut failed = 0;
+        for iteration in 0..iterations {
+            if let Err(err) = check(day, transform, &mut rng) {
+                eprintln!(
+                    "{} {}: iteration {}: {}",
+                    day.name, transform.name, iteration, err
+                );
+                failed += 1;
+            }
+        }
+        eprintln!(
+            "{} {}: {} inputs, {} failures",
+            day.name, transform.name, iterations, failed
+        );
+        failures += failed;
+    }
+    failures
+}
+
+#[test]
+fn test_metamorphic_transforms() {
+    let mut rng = Rng::new(1);
+    let s = b"1\n2\n3\n4\n";
+    let shuffled = shuffle_lines(&mut rng, s);
+    let mut sorted = lines(&shuffled);
+    sorted.sort_unstable();
+    assert_eq!(join(sorted, b"\n"), s);
+    let tile: [&[u8]; 2] = [b"ab", b"cd"];
+    let orientations: BTreeSet<_> = (0..8).map(|k| orient(&tile, k)).collect();
+    assert_eq!(orientations.len(), 8);
+    let s = b"shiny gold bags contain 2 dark red bags.\ndark red bags contain no other bags.\n";
+    assert_eq!(rename_colours(&mut rng, s), s.to_vec()); // only one colour to swap
+}
+
+#[test]
+fn test_metamorphic_answers() {
+    for transform in TRANSFORMS {
+        let day = registry::find(2020, transform.day).unwrap();
+        let mut rng = Rng::new(transform.day as u64);
+        for _ in 0..3 {
+            let result = check(day, transform, &mut rng);
+            assert_eq!(result, Ok(()), "{} {}", day.name, transform.name);
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+
+// naive solutions written straight from the puzzle statements, sharing no code with the real
+// ones; each returns `None` if the input breaks the rules of the puzzle (e.g. a malformed line or
+// more than one possible answer), in which case there's nothing to compare against
+pub type Reference = fn(&[u8]) -> Option<String>;
+
+pub fn find(year: u16, day: u8, part: u8) -> Option<Reference> {
+    if year != 2020 {
+        return None;
+    }
+    Some(match (day, part) {
+        (1, 1) => |s| day01(s, 2),
+        (1, 2) => |s| day01(s, 3),
+        (2, 1) => |s| day02(s, false),
+        (2, 2) => |s| day02(s, true),
+        (4, 1) => |s| day04(s, false),
+        (4, 2) => |s| day04(s, true),
+        (5, 1) => |s| day05(s, false),
+        (5, 2) => |s| day05(s, true),
+        (6, 1) => |s| day06(s, false),
+        (6, 2) => |s| day06(s, true),
+        (8, 1) => |s| day08(s, false),
+        (8, 2) => |s| day08(s, true),
+        (9, 1) => |s| day09(s, false),
+        (9, 2) => |s| day09(s, true),
+        (10, 1) => |s| day10(s, false),
+        (10, 2) => |s| day10(s, true),
+        (12, 1) => |s| day12(s, false),
+        (12, 2) => |s| day12(s, true),
+        (18, 1) => |s| day18(s, false),
+        (18, 2) => |s| day18(s, true),
+        _ => return None,
+    })
+}
+
+fn text(s: &[u8]) -> Option<&str> {
+    // there's something in it and it ends with a newline, optionally followed by a blank line
+    let s = std::str::from_utf8(s).ok()?.strip_suffix('\n')?;
+    let s = s.strip_suffix('\n').unwrap_or(s);
+    Some(s).filter(|s| !s.is_empty() && !s.ends_with('\n'))
+}
+
+fn lines(s: &[u8]) -> Option<Vec<&str>> {
+    let lines: Vec<_> = text(s)?.split('\n').collect();
+    Some(lines).filter(|lines| lines.iter().all(|l| !l.is_empty()))
+}
+
+fn groups(s: &[u8]) -> Option<Vec<&str>> {
+    // separated by a single blank line each, including one after the last group
+    let groups: Vec<_> = text(s)?.split("\n\n").collect();
+    let valid = |g: &&str| !g.is_empty() && !g.starts_with('\n');
+    Some(groups).filter(|groups| s.ends_with(b"\n\n") && groups.iter().all(valid))
+}
+
+fn unsigned(s: &str) -> Option<i64> {
+    // plain digits only, without the sign `str::parse` would also accept
+    Some(s)
+        .filter(|s| !s.is_empty() && s.bytes().all(|c| c.is_ascii_digit()))?
+        .parse()
+        .ok()
+}
+
+fn signed(s: &str) -> Option<i64> {
+    match (s.strip_prefix('+'), s.strip_prefix('-')) {
+        (Some(digits), _) => unsigned(digits),
+        (_, Some(digits)) => unsigned(digits).map(|x| -x),
+        _ => None,
+    }
+}
+
+fn numbers(s: &[u8], parse: fn(&str) -> Option<i64>) -> Option<Vec<i64>> {
+    lines(s)?.into_iter().map(parse).collect()
+}
+
+fn day01(s: &[u8], k: usize) -> Option<String> {
+    let x = numbers(s, |l| unsigned(l).or_else(|| signed(l).filter(|&x| x < 0)))?;
+    let n = x.len();
+    let mut found = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            if k == 2 {
+                if x[i] as i128 + x[j] as i128 == 2020 {
+                    found.push(x[i] as i128 * x[j] as i128);
+                }
+                continue;
+            }
+            for l in j + 1..n {
+                if x[i] as i128 + x[j] as i128 + x[l] as i128 == 2020 {
+                    found.push(x[i] as i128 * x[j] as i128 * x[l] as i128);
+                }
+            }
+        }
+    }
+    match found[..] {
+        [product] => Some(i64::try_from(product).ok()?.to_string()),
+        _ => None,
+    }
+}
+
+fn day02(s: &[u8], positional: bool) -> Option<String> {
+    let mut valid = 0;
+    for line in lines(s)? {
+        let (policy, pass) = line.split_once(": ")?;
+        let (range, letter) = policy.split_once(' ')?;
+        let (lo, hi) = range.split_once('-')?;
+        let (lo, hi) = (unsigned(lo)? as usize, unsigned(hi)? as usize);
+        let mut letters = letter.chars();
+        let letter = letters.next().filter(|_| letters.next().is_none())?;
+        let pass: Vec<char> = pass.chars().collect();
+        valid += if positional {
+            let at = |i: usize| i >= 1 && pass.get(i - 1) == Some(&letter);
+            at(lo) != at(hi)
+        } else {
+            (lo..=hi).contains(&pass.iter().filter(|&&c| c == letter).count())
+        } as usize;
+    }
+    Some(valid.to_string())
+}
+
+fn day04(s: &[u8], check_values: bool) -> Option<String> {
+    let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    let year = |v: &str, lo: i64, hi: i64| {
+        v.len() == 4 && matches!(unsigned(v), Some(y) if (lo..=hi).contains(&y))
+    };
+    let is_valid = |key: &str, v: &str| match key {
+        "byr" => year(v, 1920, 2002),
+        "iyr" => year(v, 2010, 2020),
+        "eyr" => year(v, 2020, 2030),
+        "hgt" => match (v.strip_suffix("cm"), v.strip_suffix("in")) {
+            (Some(cm), _) => matches!(unsigned(cm), Some(h) if (150..=193).contains(&h)),
+            (_, Some(inches)) => matches!(unsigned(inches), Some(h) if (59..=76).contains(&h)),
+            _ => false,
+        },
+        "hcl" => {
+            v.len() == 7 && v.starts_with('#') && v[1..].chars().all(|c| c.is_ascii_hexdigit())
+        }
+        "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&v),
+        "pid" => v.len() == 9 && v.chars().all(|c| c.is_ascii_digit()),
+        _ => true,
+    };
+    let mut valid = 0;
+    for passport in groups(s)? {
+        let mut fields = BTreeMap::new();
+        for field in passport.split(&[' ', '\n'][..]) {
+            let (key, value) = field.split_once(':')?;
+            let known = required.contains(&key) || key == "cid";
+            if !known || fields.insert(key, value).is_some() {
+                return None;
+            }
+        }
+        valid += required.iter().all(|&key| match fields.get(key) {
+            Some(v) => !check_values || is_valid(key, v),
+            None => false,
+        }) as usize;
+    }
+    Some(valid.to_string())
+}
+
+fn day05(s: &[u8], missing: bool) -> Option<String> {
+    let mut ids = BTreeSet::new();
+    for line in lines(s)? {
+        let (row, col) = (line.get(..7)?, line.get(7..)?);
+        let bits = |s: &str, zero, one| {
+            s.chars().try_fold(0u32, |x, c| match c {
+                _ if c == zero => Some(x * 2),
+                _ if c == one => Some(x * 2 + 1),
+                _ => None,
+            })
+        };
+        let (row, col) = (bits(row, 'F', 'B')?, bits(col, 'L', 'R')?);
+        if col >= 8 || !ids.insert(row * 8 + col) {
+            return None;
+        }
+    }
+    if !missing {
+        return ids.iter().max().map(|id| id.to_string());
+    }
+    let (&lo, &hi) = (ids.iter().next()?, ids.iter().next_back()?);
+    let gaps: Vec<_> = (lo..=hi).filter(|id| !ids.contains(id)).collect();
+    match gaps[..] {
+        [id] => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn day06(s: &[u8], everyone: bool) -> Option<String> {
+    let mut total = 0;
+    for group in groups(s)? {
+        let people: Vec<BTreeSet<char>> = group.split('\n').map(|p| p.chars().collect()).collect();
+        if people.iter().flatten().any(|c| !c.is_ascii_lowercase())
+            || people.iter().any(|p| p.is_empty())
+        {
+            return None;
+        }
+        total += ('a'..='z')
+            .filter(|c| {
+                let mut answered = people.iter().map(|p| p.contains(c));
+                if everyone {
+                    answered.all(|yes| yes)
+                } else {
+                    answered.any(|yes| yes)
+                }
+            })
+            .count();
+    }
+    Some(total.to_string())
+}
+
+fn day08(s: &[u8], fix: bool) -> Option<String> {
+    let mut program = Vec::new();
+    for line in lines(s)? {
+        let (op, arg) = line.split_once(' ')?;
+        if !["acc", "jmp", "nop"].contains(&op) {
+            return None;
+        }
+        program.push((op, signed(arg)?));
+    }
+    // the accumulator once the program repeats an instruction or leaves, and where it stopped
+    let run = |program: &[(&str, i64)]| {
+        let (mut acc, mut ip, mut seen) = (0i64, 0i64, BTreeSet::new());
+        while ip >= 0 && (ip as usize) < program.len() && seen.insert(ip) {
+            match program[ip as usize] {
+                ("acc", arg) => acc = acc.checked_add(arg)?,
+                ("jmp", arg) => ip = ip.checked_add(arg)?.checked_sub(1)?,
+                _ => {}
+            }
+            ip = ip.checked_add(1)?;
+        }
+        Some((acc, ip))
+    };
+    let len = program.len() as i64;
+    let looped = |ip: i64| (0..len).contains(&ip);
+    if !fix {
+        let (acc, ip) = run(&program)?;
+        return Some(acc.to_string()).filter(|_| looped(ip));
+    }
+    let mut fixed = Vec::new();
+    for i in 0..program.len() {
+        let mut changed = program.clone();
+        changed[i].0 = match changed[i].0 {
+            "jmp" => "nop",
+            "nop" => "jmp",
+            _ => continue,
+        };
+        match run(&changed)? {
+            (acc, ip) if ip == len => fixed.push(acc),
+            (_, ip) if !looped(ip) => return None, // jumping anywhere else isn't terminating
+            _ => {}
+        }
+    }
+    match fixed[..] {
+        [acc] => Some(acc.to_string()),
+        _ => None,
+    }
+}
+
+fn day09(s: &[u8], weakness: bool) -> Option<String> {
+    let x = numbers(s, unsigned)?;
+    let is_sum = |i: usize| {
+        let window = &x[i - 25..i];
+        window.iter().enumerate().any(|(j, &a)| {
+            window[j + 1..]
+                .iter()
+                .any(|&b| a.checked_add(b) == Some(x[i]))
+        })
+    };
+    let target = x[(25..x.len()).find(|&i| !is_sum(i))?];
+    if !weakness {
+        return Some(target.to_string());
+    }
+    let mut found = Vec::new();
+    for i in 0..x.len() {
+        let mut sum = x[i] as i128;
+        for j in i + 1..x.len() {
+            sum += x[j] as i128;
+            if sum == target as i128 {
+                let range = &x[i..=j];
+                found.push(range.iter().min()?.checked_add(*range.iter().max()?)?);
+            }
+        }
+    }
+    match found[..] {
+        [answer] => Some(answer.to_string()),
+        _ => None,
+    }
+}
+
+fn day10(s: &[u8], arrangements: bool) -> Option<String> {
+    let mut x = numbers(s, unsigned)?;
+    x.push(0);
+    x.sort_unstable();
+    x.push(x.last()?.checked_add(3)?);
+    if x.windows(2).any(|w| w[1] - w[0] == 0 || w[1] - w[0] > 3) {
+        return None;
+    }
+    if !arrangements {
+        let diffs = |d| x.windows(2).filter(|w| w[1] - w[0] == d).count();
+        return Some((diffs(1) * diffs(3)).to_string());
+    }
+    let mut ways = vec![0i64; x.len()];
+    ways[0] = 1;
+    for i in 1..x.len() {
+        for j in i.saturating_sub(3)..i {
+            if x[i] - x[j] <= 3 {
+                ways[i] = ways[i].checked_add(ways[j])?;
+            }
+        }
+    }
+    ways.last().map(|n| n.to_string())
+}
+
+fn day12(s: &[u8], waypoint: bool) -> Option<String> {
+    let (mut ship, mut dir) = ((0i64, 0i64), if waypoint { (10, 1) } else { (1, 0) });
+    for line in lines(s)? {
+        let (action, n) = (line.get(..1)?, unsigned(line.get(1..)?)?);
+        let target = if waypoint { &mut dir } else { &mut ship };
+        let moved = |(x, y): (i64, i64), (dx, dy): (i64, i64), n: i64| {
+            Some((
+                x.checked_add(dx.checked_mul(n)?)?,
+                y.checked_add(dy.checked_mul(n)?)?,
+            ))
+        };
+        *target = match action {
+            "N" => moved(*target, (0, 1), n)?,
+            "S" => moved(*target, (0, -1), n)?,
+            "E" => moved(*target, (1, 0), n)?,
+            "W" => moved(*target, (-1, 0), n)?,
+            // the only turns the puzzle has
+            "L" | "R" if [90, 180, 270].contains(&n) => {
+                let turns = if action == "L" { n / 90 } else { 4 - n / 90 };
+                for _ in 0..turns {
+                    dir = (-dir.1, dir.0);
+                }
+                continue;
+            }
+            "F" => {
+                ship = moved(ship, dir, n)?;
+                continue;
+            }
+            _ => return None,
+        };
+    }
+    let distance = ship.0.checked_abs()?.checked_add(ship.1.checked_abs()?)?;
+    Some(distance.to_string())
+}
+
+fn day18(s: &[u8], add_first: bool) -> Option<String> {
+    // one bracket level at a time: the terms in order, then the operators between them
+    fn eval(tokens: &mut std::str::Chars, depth: usize, add_first: bool) -> Option<i64> {
+        let (mut terms, mut ops) = (vec![], vec![]);
+        loop {
+            terms.push(match tokens.next()? {
+                '(' => eval(tokens, depth + 1, add_first)?,
+                c => c.to_digit(10)? as i64,
+            });
+            match tokens.next() {
+                None if depth == 0 => break,
+                Some(')') if depth > 0 => break,
+                Some(op) if op == '+' || op == '*' => ops.push(op),
+                _ => return None,
+            }
+        }
+        let mut value = terms[0];
+        let mut products = vec![];
+        for (&op, &term) in ops.iter().zip(&terms[1..]) {
+            match op {
+                '+' => value = value.checked_add(term)?,
+                _ if add_first => products.push(std::mem::replace(&mut value, term)),
+                _ => value = value.checked_mul(term)?,
+            }
+        }
+        products
+            .into_iter()
+            .try_fold(value, |acc, x| acc.checked_mul(x))
+    }
+    let mut total = 0i64;
+    for line in lines(s)? {
+        let line: String = line.chars().filter(|&c| c != ' ').collect();
+        total = total.checked_add(eval(&mut line.chars(), 0, add_first)?)?;
+    }
+    Some(total.to_string())
+}
+
+#[test]
+fn test_reference_inputs() {
+    use aoc2020::registry;
+    // every reference agrees with the real solution on the puzzle input, which obeys the rules
+    for day in registry::days(2020) {
+        for part in 1..=2 {
+            if let Some(reference) = find(2020, day.day, part) {
+                let expected = day.solver(part).unwrap()((day.input)());
+                assert_eq!(
+                    reference((day.input)()),
+                    Some(expected),
+                    "{} part{}",
+                    day.name,
+                    part
+                );
+            }
+        }
+    }
+    assert_eq!(day18(b"2 * 3 + (4 * 5)\n", false), Some("26".to_owned()));
+    assert_eq!(day18(b"2 * 3 + (4 * 5)\n", true), Some("46".to_owned()));
+    assert_eq!(day01(b"1010\n1010\n1010\n", 2), None); // three equally good pairs
+}
@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+use aoc2020::registry::{self, Day};
+
+use crate::parse_day;
+
+const HELP: &str = "\
+commands:
+  load <day> [path]         load an input (the bundled one if no path is given)
+  part <1|2>                solve one part of the loaded input
+  time [n]                  solve both parts n times and report the mean timings
+  explain                   show the steps taken to solve the loaded input, if supported
+  set <day>.<key> <value>   override a puzzle parameter, e.g. `set day23.moves 1000`
+  unset <day>.<key>         restore a puzzle parameter to its default
+  show                      show the loaded input and the puzzle parameters
+  help                      show this message
+  quit                      exit the repl";
+
+#[derive(Default)]
+struct Repl {
+    year: u16,
+    day: Option<&'static Day>,
+    input: Vec<u8>,
+    source: String,
+    overrides: BTreeMap<(u16, u8), Vec<(String, String)>>,
+}
+
+impl Repl {
+    fn new(year: u16) -> Self {
+        Self {
+            year,
+            ..Self::default()
+        }
+    }
+
+    fn split_param<'a>(&self, arg: &'a str) -> Result<(&'static Day, &'a str), String> {
+        let (day, key) = arg
+            .split_once('.')
+            .ok_or_else(|| format!("expected <day>.<key>, got: {}", arg))?;
+        let day = parse_day(self.year, day).ok_or_else(|| format!("unknown day: {}", day))?;
+        Ok((day, key))
+    }
+
+    fn overrides(&self, day: &Day) -> &[(String, String)] {
+        self.overrides
+            .get(&(day.year, day.day))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn loaded(&self) -> Result<&'static Day, String> {
+        self.day
+            .ok_or_else(|| "no input loaded, use `load <day> [path]`".to_owned())
+    }
+
+    fn load(&mut self, day: Option<&str>, path: Option<&str>) -> Result<(), String> {
+        let day = day.and_then(|day| parse_day(self.year, day));
+        let day = day.ok_or("expected a day number (1-25)")?;
+        let (input, source) = match path {
+            Some(path) => {
+                let input = fs::read(path).map_err(|err| format!("{}: {}", path, err))?;
+                (input, path.to_owned())
+            }
+            None => ((day.input)().to_vec(), "<bundled>".to_owned()),
+        };
+        println!(
+            "loaded {} ({} bytes from {})",
+            day.name,
+            input.len(),
+            source
+        );
+        self.day = Some(day);
+        self.input = input;
+        self.source = source;
+        Ok(())
+    }
+
+    fn solve(&self, part: u8) -> Result<(String, Duration), String> {
+        let day = self.loaded()?;
+        let overrides = self.overrides(day);
+        // a panic on a loaded file only ends up here with unwinding (the `fuzz` profile); a
+        // release build aborts, and without `checked` bad input may be misread instead
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            day.solve_timed_with(part, &self.input, overrides)
+        }))
+        .map_err(|_| "solver panicked".to_owned())?
+        .ok_or("expected part 1 or 2")?
+        .map_err(|err| err.to_string())
+    }
+
+    fn part(&self, part: Option<&str>) -> Result<(), String> {
+        let part = part
+            .and_then(|p| p.parse().ok())
+            .ok_or("expected part 1 or 2")?;
+        let (answer, elapsed) = self.solve(part)?;
+        println!("part{} = {} ({:?})", part, answer, elapsed);
+        Ok(())
+    }
+
+    fn time(&self, n: Option<&str>) -> Result<(), String> {
+        let n = n
+            .map_or(Ok(1), str::parse::<u32>)
+            .map_err(|_| "expected a count")?
+            .max(1);
+        for part in 1..=2 {
+            let mut total = Duration::default();
+            let mut answer = String::new();
+            for _ in 0..n {
+                let (a, elapsed) = self.solve(part)?;
+                total += elapsed;
+                answer = a;
+            }
+            println!(
+                "part{} = {} ({:?} mean over {})",
+                part,
+                answer,
+                total / n,
+                n
+            );
+        }
+        Ok(())
+    }
+
+    fn explain(&self) -> Result<(), String> {
+        let day = self.loaded()?;
+        let explain = day
+            .explain
+            .ok_or_else(|| format!("{} has nothing to explain", day.name))?;
+        // same caveats as in `solve`
+        let trace = panic::catch_unwind(|| explain(&self.input))
+            .map_err(|_| "solver panicked".to_owned())?;
+        print!("{}", trace);
+        Ok(())
+    }
+
+    fn set(&mut self, param: Option<&str>, value: Option<&str>) -> Result<(), String> {
+        let (param, value) = param.zip(value).ok_or("expected <day>.<key> <value>")?;
+        let (day, key) = self.split_param(param)?;
+        let mut overrides = self.overrides(day).to_vec();
+        overrides.retain(|(k, _)| k != key);
+        overrides.push((key.to_owned(), value.to_owned()));
+        (day.params)(&overrides).map_err(|err| err.to_string())?;
+        self.overrides.insert((day.year, day.day), overrides);
+        Ok(())
+    }
+
+    fn unset(&mut self, param: Option<&str>) -> Result<(), String> {
+        let (day, key) = self.split_param(param.ok_or("expected <day>.<key>")?)?;
+        if let Some(overrides) = self.overrides.get_mut(&(day.year, day.day)) {
+            overrides.retain(|(k, _)| k != key);
+        }
+        Ok(())
+    }
+
+    fn show(&self) {
+        match self.day {
+            Some(day) => println!(
+                "{}: {} bytes from {}",
+                day.name,
+                self.input.len(),
+                self.source
+            ),
+            None => println!("no input loaded"),
+        }
+        let days = self.day.iter().map(|&day| (day.year, day.day));
+        let mut days = days
+            .chain(self.overrides.keys().copied())
+            .collect::<Vec<_>>();
+        days.sort_unstable();
+        days.dedup();
+        for day in days
+            .into_iter()
+            .filter_map(|(year, day)| registry::find(year, day))
+        {
+            let values = (day.params)(self.overrides(day)).unwrap_or_default();
+            for (key, value) in values {
+                println!("  {}.{} = {}", day.name, key, value);
+            }
+        }
+    }
+
+    fn execute(&mut self, line: &str) -> Result<bool, String> {
+        let mut words = line.split_whitespace();
+        let (cmd, arg1, arg2) = (words.next(), words.next(), words.next());
+        match cmd {
+            None => {}
+            Some("load") => self.load(arg1, arg2)?,
+            Some("part") => self.part(arg1)?,
+            Some("time") => self.time(arg1)?,
+            Some("explain") => self.explain()?,
+            Some("set") => self.set(arg1, arg2)?,
+            Some("unset") => self.unset(arg1)?,
+            Some("show") => self.show(),
+            Some("help") => println!("{}", HELP),
+            Some("quit") | Some("exit") => return Ok(false),
+            Some(cmd) => return Err(format!("unknown command: {} (try `help`)", cmd)),
+        }
+        Ok(true)
+    }
+}
+
+pub fn repl(year: u16) -> io::Result<()> {
+    let mut repl = Repl::new(year);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(()),
+        };
+        match repl.execute(&line) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(err) => println!("error: {}", err),
+        }
+    }
+}
+
+#[test]
+fn test_repl_session() {
+    let mut repl = Repl::new(2020);
+    assert!(repl.execute("part 1").is_err());
+    repl.execute("load 23").unwrap();
+    repl.execute("set day23.moves 10").unwrap();
+    repl.input = b"389125467".to_vec();
+    assert_eq!(repl.solve(1).unwrap().0, "92658374");
+    assert!(repl.execute("set day23.speed 1").is_err());
+    repl.execute("unset day23.moves").unwrap();
+    assert_eq!(repl.solve(1).unwrap().0, "67384529");
+}
@@ -0,0 +1,290 @@
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use aoc2020::registry::Day;
+
+use crate::{parse_day, SOLVER_STACK_SIZE};
+
+pub const DEFAULT_PORT: u16 = 2020;
+
+const MAX_BODY_LEN: usize = 64 << 20;
+const MAX_LINE_LEN: usize = 8 << 10;
+const MAX_HEADERS: usize = 100;
+
+// every connection is solved on its own thread with a large stack, so only a few at a time;
+// the ones past that are turned away right away
+const MAX_CONNECTIONS: usize = 8;
+
+static CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(body: String) -> Self {
+        Self {
+            status: "200 OK",
+            body,
+        }
+    }
+
+    fn error(status: &'static str, message: &str) -> Self {
+        let body = format!("{{\"error\":{}}}", json_string(message));
+        Self { status, body }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn read_line(stream: &mut impl BufRead, line: &mut String) -> io::Result<Result<(), Response>> {
+    // a line that doesn't end within `MAX_LINE_LEN` bytes is rejected rather than buffered
+    line.clear();
+    let n = (&mut *stream).take(MAX_LINE_LEN as u64).read_line(line)?;
+    if n == MAX_LINE_LEN && !line.ends_with('\n') {
+        return Ok(Err(Response::error(
+            "431 Request Header Fields Too Large",
+            "line too long",
+        )));
+    }
+    Ok(Ok(()))
+}
+
+fn read_request(stream: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let malformed = || Ok(Err(Response::error("400 Bad Request", "malformed request")));
+    let mut line = String::new();
+    if let Err(response) = read_line(stream, &mut line)? {
+        return Ok(Err(response));
+    }
+    let mut parts = line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_owned(), path.to_owned()),
+        _ => return malformed(),
+    };
+    let mut content_length = 0;
+    for i in 0.. {
+        if let Err(response) = read_line(stream, &mut line)? {
+            return Ok(Err(response));
+        }
+        if line.trim_end().is_empty() {
+            break;
+        }
+        if i == MAX_HEADERS {
+            return Ok(Err(Response::error(
+                "431 Request Header Fields Too Large",
+                "too many headers",
+            )));
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case("content-length") {
+                content_length = match value.trim().parse() {
+                    Ok(n) => n,
+                    Err(_) => return malformed(),
+                };
+            }
+        }
+    }
+    if content_length > MAX_BODY_LEN {
+        return Ok(Err(Response::error(
+            "413 Payload Too Large",
+            "input too large",
+        )));
+    }
+    let mut body = vec![0; content_length];
+    stream.read_exact(&mut body)?;
+    Ok(Ok(Request { method, path, body }))
+}
+
+fn solve(day: &Day, body: &[u8]) -> Response {
+    let mut json = format!("{{\"day\":{}", day.day);
+    for part in 1..=2 {
+        // a malformed input may trip one of the solvers; that's only caught with unwinding (the
+        // `fuzz` profile) and the `checked` bounds checks, a release build aborts or misreads
+        match panic::catch_unwind(|| day.solve_timed(part, body).unwrap()) {
+            Ok((answer, elapsed)) => write!(
+                json,
+                ",\"part{}\":{{\"answer\":{},\"time_ns\":{}}}",
+                part,
+                json_string(&answer),
+                elapsed.as_nanos()
+            )
+            .unwrap(),
+            Err(_) => return Response::error("500 Internal Server Error", "solver panicked"),
+        }
+    }
+    json.push('}');
+    Response::json(json)
+}
+
+fn route(request: &Request, year: u16) -> Response {
+    // either `/day/{n}` for the default year or `/year/{y}/day/{n}`
+    let path = request.path.split('/').skip(1).collect::<Vec<_>>();
+    let (year, day) = match path[..] {
+        ["day", day] => (year, day),
+        ["year", y, "day", day] => match y.parse() {
+            Ok(y) => (y, day),
+            Err(_) => return Response::error("404 Not Found", "unknown year"),
+        },
+        _ => return Response::error("404 Not Found", "not found"),
+    };
+    let day = match parse_day(year, day) {
+        Some(day) => day,
+        None => return Response::error("404 Not Found", "unknown day"),
+    };
+    if request.method != "POST" {
+        return Response::error("405 Method Not Allowed", "expected POST");
+    }
+    if request.body.is_empty() {
+        return Response::error("400 Bad Request", "empty input");
+    }
+    solve(day, &request.body)
+}
+
+fn handle(stream: TcpStream, year: u16) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader)? {
+        Ok(request) => route(&request, year),
+        Err(response) => response,
+    };
+    respond(stream, &response)
+}
+
+fn respond(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+// releases a connection slot when its thread is done, panicking or not
+struct Slot;
+
+impl Slot {
+    fn take() -> Option<Self> {
+        let taken = CONNECTIONS.fetch_add(1, Ordering::SeqCst);
+        if taken < MAX_CONNECTIONS {
+            Some(Slot)
+        } else {
+            CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
+            None
+        }
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        CONNECTIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn serve(port: u16, year: u16) -> io::Result<()> {
+    // the parsers skip bounds checks and a release build aborts on panics, so without both the
+    // `checked` feature and unwinding, any client could crash the server or worse
+    if !cfg!(feature = "checked") || !cfg!(panic = "unwind") {
+        return Err(io::Error::other(
+            "serve needs a build that survives malformed inputs: \
+             cargo run --profile fuzz --features checked --bin run -- serve",
+        ));
+    }
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    eprintln!("listening on http://{}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let slot = match Slot::take() {
+            Some(slot) => slot,
+            None => {
+                let busy = Response::error("503 Service Unavailable", "too many connections");
+                if let Err(err) = respond(stream, &busy) {
+                    eprintln!("error: {}", err);
+                }
+                continue;
+            }
+        };
+        thread::Builder::new()
+            .stack_size(SOLVER_STACK_SIZE)
+            .spawn(move || {
+                let _slot = slot;
+                if let Err(err) = handle(stream, year) {
+                    eprintln!("error: {}", err);
+                }
+            })?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_serve_route() {
+    let body = aoc2020::year2020::day08::input().to_vec();
+    let request = Request {
+        method: "POST".into(),
+        path: "/year/2020/day/8".into(),
+        body,
+    };
+    let response = route(&request, 2020);
+    assert_eq!(response.status, "200 OK");
+    assert!(response
+        .body
+        .starts_with("{\"day\":8,\"part1\":{\"answer\":\"2058\""));
+    assert!(response.body.contains("\"part2\":{\"answer\":\"1000\""));
+}
+
+#[test]
+fn test_serve_read_request() {
+    let raw = b"POST /day/26 HTTP/1.1\r\nHost: x\r\nContent-Length: 3\r\n\r\n1\n2";
+    let request = read_request(&mut &raw[..]).unwrap().unwrap();
+    assert_eq!(request.body, b"1\n2");
+    assert_eq!(route(&request, 2020).status, "404 Not Found");
+    let status = |raw: &[u8]| read_request(&mut &raw[..]).unwrap().unwrap_err().status;
+    let raw = format!(
+        "POST /day/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+        1 << 30
+    );
+    assert_eq!(status(raw.as_bytes()), "413 Payload Too Large");
+    let raw = format!(
+        "POST /day/1 HTTP/1.1\r\nX: {}\r\n\r\n",
+        "x".repeat(MAX_LINE_LEN)
+    );
+    assert_eq!(
+        status(raw.as_bytes()),
+        "431 Request Header Fields Too Large"
+    );
+    let raw = b"POST /day/1 HTTP/1.1\r\nContent-Length: lots\r\n\r\n";
+    assert_eq!(status(raw), "400 Bad Request");
+    let slots: Vec<_> = (0..MAX_CONNECTIONS).map_while(|_| Slot::take()).collect();
+    assert!(Slot::take().is_none());
+    drop(slots);
+    assert!(Slot::take().is_some());
+}
@@ -0,0 +1,135 @@
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+
+use aoc2020::registry::Day;
+
+use crate::{Overrides, SOLVER_STACK_SIZE};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+type Answer = Result<String, String>;
+
+// re-reads the file on every poll, the inputs are small enough for that to be cheaper than
+// trusting modification times with a one-second resolution
+struct Watcher {
+    path: String,
+    contents: Option<Vec<u8>>,
+    error: Option<String>,
+}
+
+impl Watcher {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_owned(),
+            contents: None,
+            error: None,
+        }
+    }
+
+    fn poll(&mut self) -> Option<&[u8]> {
+        match fs::read(&self.path) {
+            Ok(contents) if self.contents.as_ref() != Some(&contents) => {
+                self.contents = Some(contents);
+                self.error = None;
+                self.contents.as_deref()
+            }
+            Ok(_) => None,
+            Err(err) => {
+                // editors often replace the file on save, so it may be briefly missing
+                let err = format!("{}: {}", self.path, err);
+                if self.error.as_ref() != Some(&err) {
+                    eprintln!("error: {}", err);
+                    self.error = Some(err);
+                }
+                None
+            }
+        }
+    }
+}
+
+fn solve(day: &Day, part: u8, s: &[u8], overrides: &Overrides) -> (Answer, Duration) {
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| {
+        day.solve_timed_with(part, s, overrides).unwrap()
+    }));
+    match solved {
+        Ok(Ok((answer, elapsed))) => (Ok(answer), elapsed),
+        Ok(Err(err)) => (Err(err.to_string()), Duration::default()),
+        Err(_) => (Err("solver panicked".to_owned()), Duration::default()),
+    }
+}
+
+fn describe(answer: &Answer, previous: Option<&Answer>) -> String {
+    let show = |answer: &Answer| match answer {
+        Ok(answer) => answer.clone(),
+        Err(err) => format!("error: {}", err),
+    };
+    match previous {
+        None => show(answer),
+        Some(previous) if previous == answer => format!("{} (unchanged)", show(answer)),
+        Some(previous) => format!("{} (was {})", show(answer), show(previous)),
+    }
+}
+
+fn watch_file(day: &Day, path: &str, overrides: &Overrides) {
+    eprintln!("watching {} for {}, press ctrl-c to stop", path, day.name);
+    let mut watcher = Watcher::new(path);
+    let mut previous: Option<[Answer; 2]> = None;
+    loop {
+        if let Some(s) = watcher.poll() {
+            println!("{}: {} bytes", path, s.len());
+            let (answer1, elapsed1) = solve(day, 1, s, overrides);
+            let (answer2, elapsed2) = solve(day, 2, s, overrides);
+            let (previous1, previous2) = match &previous {
+                Some([previous1, previous2]) => (Some(previous1), Some(previous2)),
+                None => (None, None),
+            };
+            println!(
+                "  part1 = {} ({:?})",
+                describe(&answer1, previous1),
+                elapsed1
+            );
+            println!(
+                "  part2 = {} ({:?})",
+                describe(&answer2, previous2),
+                elapsed2
+            );
+            previous = Some([answer1, answer2]);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+pub fn watch(day: &'static Day, path: &str, overrides: Overrides) -> io::Result<()> {
+    let path = path.to_owned();
+    let watcher = thread::Builder::new()
+        .stack_size(SOLVER_STACK_SIZE)
+        .spawn(move || watch_file(day, &path, &overrides))?;
+    let _ = watcher.join();
+    Ok(())
+}
+
+#[test]
+fn test_watch_describe() {
+    let (a, b) = (Ok("1".to_owned()), Ok("2".to_owned()));
+    assert_eq!(describe(&a, None), "1");
+    assert_eq!(describe(&a, Some(&a)), "1 (unchanged)");
+    assert_eq!(describe(&b, Some(&a)), "2 (was 1)");
+    let err = Err("solver panicked".to_owned());
+    assert_eq!(describe(&err, Some(&b)), "error: solver panicked (was 2)");
+}
+
+#[test]
+fn test_watch_poll() {
+    let path = std::env::temp_dir().join(format!("aoc2020-watch-{}.txt", std::process::id()));
+    let mut watcher = Watcher::new(path.to_str().unwrap());
+    assert_eq!(watcher.poll(), None);
+    fs::write(&path, "1\n").unwrap();
+    assert_eq!(watcher.poll(), Some(&b"1\n"[..]));
+    assert_eq!(watcher.poll(), None);
+    fs::write(&path, "2\n").unwrap();
+    assert_eq!(watcher.poll(), Some(&b"2\n"[..]));
+    fs::remove_file(&path).unwrap();
+}
@@ -1,10 +1,22 @@
+#[cfg(not(feature = "checked"))]
 use core::hint::unreachable_unchecked;
 use core::ops::{Add, AddAssign, Mul};
+#[cfg(not(feature = "checked"))]
 use core::slice;
+use std::fmt::Display;
+use std::io::{self, BufRead};
 
+use arrayvec::{Array, ArrayVec};
 use memchr::{memchr, memchr2};
 
+mod bitset;
+mod grid;
+
+pub use self::bitset::BitSet;
+pub use self::grid::{read_lane, write_lane, Grid, Lane};
+
 #[inline(always)]
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn parse_int_fast_skip_custom<T>(
     s: &mut &[u8],
     min_digits: usize,
@@ -31,11 +43,13 @@ where
             return v;
         }
     }
-    *s = s.advance(skip);
+    // the last number may not be followed by anything
+    *s = s.advance(skip.min(s.len()));
     v
 }
 
 #[inline(always)]
+#[cfg_attr(feature = "checked", track_caller)]
 pub fn parse_int_fast<T>(s: &mut &[u8], min_digits: usize, max_digits: usize) -> T
 where
     T: From<u8> + Add<Output = T> + Mul<Output = T>,
@@ -46,90 +60,278 @@ where
 pub trait SliceExt<T: Copy> {
     fn get_len(&self) -> usize;
     fn get_at(&self, i: usize) -> T;
+    fn get_ref_at(&self, i: usize) -> &T;
     fn get_mut_at(&mut self, i: usize) -> &mut T;
     fn set_at(&mut self, i: usize, v: T);
     fn advance(&self, n: usize) -> &Self;
-    fn add_at(&mut self, i: usize, v: T);
+    fn add_at(&mut self, i: usize, v: T)
+    where
+        T: AddAssign;
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn get_first(&self) -> T {
         self.get_at(0)
     }
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn get_last(&self) -> T {
         self.get_at(self.get_len() - 1)
     }
 }
 
-impl<T: Copy + Add<Output = T> + AddAssign> SliceExt<T> for [T] {
+impl<T: Copy> SliceExt<T> for [T] {
     #[inline]
     fn get_len(&self) -> usize {
         self.len()
     }
 
     #[inline]
+    #[cfg(not(feature = "checked"))]
     fn get_at(&self, i: usize) -> T {
         unsafe { *self.get_unchecked(i) }
     }
 
     #[inline]
+    #[cfg(not(feature = "checked"))]
+    fn get_ref_at(&self, i: usize) -> &T {
+        unsafe { self.get_unchecked(i) }
+    }
+
+    #[inline]
+    #[cfg(not(feature = "checked"))]
     fn get_mut_at(&mut self, i: usize) -> &mut T {
         unsafe { self.get_unchecked_mut(i) }
     }
 
     #[inline]
+    #[cfg(not(feature = "checked"))]
     fn set_at(&mut self, i: usize, v: T) {
         unsafe { *self.get_unchecked_mut(i) = v };
     }
 
     #[inline]
+    #[cfg(not(feature = "checked"))]
     fn advance(&self, n: usize) -> &Self {
         unsafe { slice::from_raw_parts(self.as_ptr().add(n), self.len().saturating_sub(n)) }
     }
 
     #[inline]
-    fn add_at(&mut self, i: usize, v: T) {
+    #[cfg(not(feature = "checked"))]
+    fn add_at(&mut self, i: usize, v: T)
+    where
+        T: AddAssign,
+    {
         unsafe { *self.get_unchecked_mut(i) += v };
     }
+
+    // with the `checked` feature, out-of-bounds access panics instead of being undefined behavior
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn get_at(&self, i: usize) -> T {
+        self[i]
+    }
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn get_ref_at(&self, i: usize) -> &T {
+        &self[i]
+    }
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn get_mut_at(&mut self, i: usize) -> &mut T {
+        &mut self[i]
+    }
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn set_at(&mut self, i: usize, v: T) {
+        self[i] = v;
+    }
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn advance(&self, n: usize) -> &Self {
+        &self[n..]
+    }
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn add_at(&mut self, i: usize, v: T)
+    where
+        T: AddAssign,
+    {
+        self[i] += v;
+    }
+}
+
+pub trait ArrayVecExt<T> {
+    fn push_fast(&mut self, v: T);
+}
+
+impl<A: Array> ArrayVecExt<A::Item> for ArrayVec<A> {
+    // pushing past the capacity panics with `checked` and is undefined behavior without it
+
+    #[inline]
+    #[cfg(not(feature = "checked"))]
+    fn push_fast(&mut self, v: A::Item) {
+        unsafe { self.push_unchecked(v) };
+    }
+
+    #[inline]
+    #[cfg(feature = "checked")]
+    #[track_caller]
+    fn push_fast(&mut self, v: A::Item) {
+        self.push(v);
+    }
 }
 
 pub trait ByteSliceExt: SliceExt<u8> {
     fn memchr(&self, c: u8) -> usize;
     fn memchr2(&self, c1: u8, c2: u8) -> usize;
     fn get_u16_ne(&self) -> u16;
+    fn get_u64_be(&self) -> u64;
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn get_digit(&self) -> u8 {
         self.get_first().wrapping_sub(b'0')
     }
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn get_digit_at(&self, i: usize) -> u8 {
         self.get_at(i).wrapping_sub(b'0')
     }
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn skip_past(&self, c: u8, i: usize) -> &Self {
         self.advance(1 + i + self.memchr(c))
     }
 }
 
+#[inline]
+#[cfg(not(feature = "checked"))]
+fn unwrap_found(i: Option<usize>) -> usize {
+    i.unwrap_or_else(|| unsafe { unreachable_unchecked() })
+}
+
+#[inline]
+#[cfg(feature = "checked")]
+#[track_caller]
+fn unwrap_found(i: Option<usize>) -> usize {
+    i.expect("delimiter not found")
+}
+
 impl ByteSliceExt for [u8] {
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn memchr(&self, c: u8) -> usize {
-        memchr(c, self).unwrap_or_else(|| unsafe { unreachable_unchecked() })
+        unwrap_found(memchr(c, self))
     }
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn memchr2(&self, c1: u8, c2: u8) -> usize {
-        memchr2(c1, c2, self).unwrap_or_else(|| unsafe { unreachable_unchecked() })
+        unwrap_found(memchr2(c1, c2, self))
     }
 
     #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
     fn get_u16_ne(&self) -> u16 {
         let mut a = [0; 2];
         a.copy_from_slice(&self[..2]);
         u16::from_ne_bytes(a)
     }
+
+    #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
+    fn get_u64_be(&self) -> u64 {
+        let mut a = [0; 8];
+        a.copy_from_slice(&self[..8]);
+        u64::from_be_bytes(a)
+    }
+}
+
+#[inline]
+fn read_line_terminated<R: BufRead>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<bool> {
+    // reads the next line into the buffer, making sure it's always terminated with '\n'
+    buf.clear();
+    if reader.read_until(b'\n', buf)? == 0 {
+        return Ok(false);
+    }
+    if buf.last() != Some(&b'\n') {
+        buf.push(b'\n');
+    }
+    Ok(true)
+}
+
+pub fn for_each_line<R: BufRead>(mut reader: R, mut f: impl FnMut(&[u8])) -> io::Result<()> {
+    // calls `f` on every non-empty line (including the trailing '\n'), reusing the buffer
+    let mut buf = Vec::with_capacity(256);
+    while read_line_terminated(&mut reader, &mut buf)? {
+        if buf.len() > 1 {
+            f(&buf);
+        }
+    }
+    Ok(())
+}
+
+pub fn for_each_group<R: BufRead>(mut reader: R, mut f: impl FnMut(&[u8])) -> io::Result<()> {
+    // calls `f` on every group of lines separated by blank lines; each group is passed
+    // along with its terminating blank line, i.e. ending with "\n\n"
+    let (mut group, mut line) = (Vec::with_capacity(1024), Vec::with_capacity(256));
+    while read_line_terminated(&mut reader, &mut line)? {
+        if line.len() > 1 {
+            group.extend_from_slice(&line);
+        } else if !group.is_empty() {
+            group.push(b'\n');
+            f(&group);
+            group.clear();
+        }
+    }
+    if !group.is_empty() {
+        group.push(b'\n');
+        f(&group);
+    }
+    Ok(())
+}
+
+#[inline]
+pub fn to_lines<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+    // the inverse of parsing one item per line, each line terminated with '\n'
+    items
+        .into_iter()
+        .map(|item| format!("{}\n", item))
+        .collect()
+}
+
+#[cfg(test)]
+#[track_caller]
+pub fn assert_round_trip(s: &[u8], round_trip: impl Fn(&[u8]) -> String) -> String {
+    // `round_trip` parses and unparses; whatever it loses is gone after the first pass, so a
+    // second one has to give the same text back, which is returned for day-specific checks
+    let text = round_trip(s);
+    assert_eq!(round_trip(text.as_bytes()), text);
+    text
+}
+
+#[cfg(test)]
+#[track_caller]
+pub fn assert_lossless_round_trip(s: &[u8], round_trip: impl Fn(&[u8]) -> String) {
+    // the same, for inputs that come back as they were read; some end in blank lines that
+    // aren't written back
+    use bstr::ByteSlice;
+    let text = assert_round_trip(s, round_trip);
+    assert_eq!(text.as_bytes().trim_end(), s.trim_end());
 }
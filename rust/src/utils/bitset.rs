@@ -0,0 +1,322 @@
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+const WORD_BITS: usize = 64;
+const INLINE_WORDS: usize = 4;
+
+#[derive(Debug, Clone)]
+enum Words {
+    Inline([u64; INLINE_WORDS]),
+    Heap(Vec<u64>),
+}
+
+// a set of small non-negative integers, one bit each; elements below 256 are stored inline
+// and the set moves to the heap as soon as a larger one is inserted
+#[derive(Debug, Clone)]
+pub struct BitSet(Words);
+
+#[inline]
+fn split(i: usize) -> (usize, u64) {
+    (i / WORD_BITS, 1 << (i % WORD_BITS))
+}
+
+impl BitSet {
+    #[inline]
+    pub fn new() -> Self {
+        Self(Words::Inline([0; INLINE_WORDS]))
+    }
+
+    #[inline]
+    pub fn with_capacity(n: usize) -> Self {
+        let mut set = Self::new();
+        set.reserve(n);
+        set
+    }
+
+    #[inline]
+    pub fn full(n: usize) -> Self {
+        // all elements in 0..n
+        let mut set = Self::with_capacity(n);
+        let (n_words, rem) = (n / WORD_BITS, n % WORD_BITS);
+        let words = set.words_mut();
+        for word in &mut words[..n_words] {
+            *word = !0;
+        }
+        if rem > 0 {
+            words[n_words] = (1 << rem) - 1;
+        }
+        set
+    }
+
+    #[inline]
+    pub fn words(&self) -> &[u64] {
+        match self.0 {
+            Words::Inline(ref words) => words,
+            Words::Heap(ref words) => words,
+        }
+    }
+
+    #[inline]
+    fn words_mut(&mut self) -> &mut [u64] {
+        match self.0 {
+            Words::Inline(ref mut words) => words,
+            Words::Heap(ref mut words) => words,
+        }
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.words().len() * WORD_BITS
+    }
+
+    #[inline]
+    pub fn reserve(&mut self, n: usize) {
+        // makes room for all elements in 0..n
+        let n_words = n.div_ceil(WORD_BITS);
+        if n_words <= self.words().len() {
+            return;
+        }
+        match self.0 {
+            Words::Inline(words) => {
+                let mut heap = Vec::with_capacity(n_words);
+                heap.extend_from_slice(&words);
+                heap.resize(n_words, 0);
+                self.0 = Words::Heap(heap);
+            }
+            Words::Heap(ref mut words) => words.resize(n_words, 0),
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, i: usize) -> bool {
+        // returns true if the element wasn't there yet
+        self.reserve(i + 1);
+        let (w, bit) = split(i);
+        let word = &mut self.words_mut()[w];
+        let is_new = *word & bit == 0;
+        *word |= bit;
+        is_new
+    }
+
+    #[inline]
+    pub fn remove(&mut self, i: usize) -> bool {
+        // returns true if the element was there
+        let (w, bit) = split(i);
+        match self.words_mut().get_mut(w) {
+            Some(word) => {
+                let was_set = *word & bit != 0;
+                *word &= !bit;
+                was_set
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, i: usize) {
+        self.reserve(i + 1);
+        let (w, bit) = split(i);
+        self.words_mut()[w] ^= bit;
+    }
+
+    #[inline]
+    pub fn contains(&self, i: usize) -> bool {
+        let (w, bit) = split(i);
+        self.words().get(w).is_some_and(|&word| word & bit != 0)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words().iter().all(|&w| w == 0)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        for word in self.words_mut() {
+            *word = 0;
+        }
+    }
+
+    #[inline]
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            words: self.words(),
+            base: 0,
+            word: 0,
+        }
+    }
+
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        self.reserve(other.capacity());
+        for (a, &b) in self.words_mut().iter_mut().zip(other.words()) {
+            *a |= b;
+        }
+    }
+
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        let other = other.words();
+        for (i, a) in self.words_mut().iter_mut().enumerate() {
+            *a &= other.get(i).copied().unwrap_or(0);
+        }
+    }
+
+    #[inline]
+    pub fn difference_with(&mut self, other: &Self) {
+        // and-not: removes all elements of `other`
+        for (a, &b) in self.words_mut().iter_mut().zip(other.words()) {
+            *a &= !b;
+        }
+    }
+
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        let mut words = self.words().iter().zip(other.words());
+        words.all(|(&a, &b)| a & b == 0)
+    }
+}
+
+impl Default for BitSet {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        // the sets may have different capacities, missing words are zero
+        let (a, b) = (self.words(), other.words());
+        let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+        short == &long[..short.len()] && long[short.len()..].iter().all(|&w| w == 0)
+    }
+}
+
+impl Eq for BitSet {}
+
+pub struct Iter<'a> {
+    words: &'a [u64],
+    base: usize,
+    word: u64,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        // `base` is one word past the start of the current word
+        while self.word == 0 {
+            let (&word, rest) = self.words.split_first()?;
+            self.word = word;
+            self.words = rest;
+            self.base += WORD_BITS;
+        }
+        let i = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1; // clear the lowest set bit
+        Some(self.base - WORD_BITS + i)
+    }
+}
+
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl Extend<usize> for BitSet {
+    #[inline]
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $with:ident) => {
+        impl $op_assign<&BitSet> for BitSet {
+            #[inline]
+            fn $f_assign(&mut self, other: &BitSet) {
+                self.$with(other);
+            }
+        }
+
+        impl $op for &BitSet {
+            type Output = BitSet;
+
+            #[inline]
+            fn $f(self, other: &BitSet) -> BitSet {
+                let mut out = self.clone();
+                out.$with(other);
+                out
+            }
+        }
+    };
+}
+
+impl_op!(BitOr, bitor, BitOrAssign, bitor_assign, union_with);
+impl_op!(BitAnd, bitand, BitAndAssign, bitand_assign, intersect_with);
+impl_op!(Sub, sub, SubAssign, sub_assign, difference_with);
+
+#[test]
+fn test_bitset_basic() {
+    let mut set = BitSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(3));
+    assert!(!set.insert(3));
+    assert!(set.insert(255));
+    assert_eq!(set.capacity(), 256);
+    assert!(set.insert(1000)); // spills to the heap
+    assert!(set.capacity() > 1000);
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 255, 1000]);
+    assert!(set.contains(1000) && !set.contains(999) && !set.contains(1 << 20));
+    assert!(set.remove(255));
+    assert!(!set.remove(1 << 20));
+    set.toggle(3);
+    assert_eq!(set.first(), Some(1000));
+    assert_eq!(BitSet::full(70).len(), 70);
+    assert_eq!(BitSet::full(64).iter().last(), Some(63));
+}
+
+#[test]
+fn test_bitset_ops() {
+    let a: BitSet = vec![1, 2, 3, 300].into_iter().collect();
+    let b: BitSet = vec![2, 3, 4].into_iter().collect();
+    assert_eq!(&a | &b, vec![1, 2, 3, 4, 300].into_iter().collect());
+    assert_eq!(&a & &b, vec![2, 3].into_iter().collect());
+    assert_eq!(&b & &a, vec![2, 3].into_iter().collect());
+    assert_eq!(&a - &b, vec![1, 300].into_iter().collect());
+    assert_eq!(&b - &a, vec![4].into_iter().collect());
+    assert!(!a.is_disjoint(&b));
+    assert!((&a - &b).is_disjoint(&b));
+    let mut c = b.clone();
+    c |= &a;
+    c -= &b;
+    assert_eq!(c, &a - &b);
+}
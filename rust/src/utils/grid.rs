@@ -0,0 +1,319 @@
+use packed_simd_2::{u8x16, u8x32, u8x64};
+
+// a simd vector that can be loaded from / stored to a run of grid cells; `read` and `write`
+// don't check the length (`read_lane` and `write_lane` below are the checked way in)
+pub trait Lane<T>: Copy {
+    const LANES: usize;
+
+    /// # Safety
+    /// `s` must hold at least `LANES` elements.
+    unsafe fn read(s: &[T]) -> Self;
+
+    /// # Safety
+    /// `s` must hold at least `LANES` elements.
+    unsafe fn write(self, s: &mut [T]);
+}
+
+macro_rules! impl_lane {
+    ($($simd:ident => $t:ty),*) => {$(
+        impl Lane<$t> for $simd {
+            const LANES: usize = $simd::lanes();
+
+            #[inline]
+            unsafe fn read(s: &[$t]) -> Self {
+                $simd::from_slice_unaligned_unchecked(s)
+            }
+
+            #[inline]
+            unsafe fn write(self, s: &mut [$t]) {
+                self.write_to_slice_unaligned_unchecked(s)
+            }
+        }
+    )*};
+}
+
+impl_lane!(u8x16 => u8, u8x32 => u8, u8x64 => u8);
+
+#[inline]
+#[cfg_attr(feature = "checked", track_caller)]
+fn check_lane<T, V: Lane<T>>(s: &[T], pos: usize) {
+    // like the slice helpers, only checked in `checked` (and debug) builds
+    let fits = pos <= s.len() && V::LANES <= s.len() - pos;
+    if cfg!(feature = "checked") {
+        assert!(fits, "lane out of bounds");
+    } else {
+        debug_assert!(fits, "lane out of bounds");
+    }
+}
+
+#[inline]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn read_lane<T, V: Lane<T>>(s: &[T], pos: usize) -> V {
+    // reads `V::LANES` elements starting at `pos` (unaligned)
+    check_lane::<T, V>(s, pos);
+    unsafe { V::read(s.get_unchecked(pos..)) }
+}
+
+#[inline]
+#[cfg_attr(feature = "checked", track_caller)]
+pub fn write_lane<T, V: Lane<T>>(s: &mut [T], pos: usize, v: V) {
+    check_lane::<T, V>(s, pos);
+    unsafe { v.write(s.get_unchecked_mut(pos..)) }
+}
+
+// offsets of the 8 neighbours of a cell, clockwise from the top-left one
+const NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+// a 2d grid with `pad` cells of padding on each side; the inside of each row is covered by a
+// whole number of `lane`-wide vectors and the row stride (padding included) is rounded up to a
+// multiple of `lane`, so the inside can be processed vector by vector, every row starts at the
+// same alignment and neighbours within `pad` can be read without checks;
+// coordinates are relative to the top-left inner cell, so padding cells are at negative ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    pad: usize,
+    lanes: usize,
+    lane: usize,
+}
+
+impl<T: Copy> Grid<T> {
+    #[inline]
+    pub fn new(width: usize, height: usize, pad: usize, lane: usize, fill: T) -> Self {
+        let lane = lane.max(1);
+        let lanes = width.div_ceil(lane);
+        let stride = (2 * pad + lanes * lane).next_multiple_of(lane);
+        let cells = vec![fill; stride * (height + 2 * pad)];
+        Self {
+            cells,
+            width,
+            height,
+            pad,
+            lanes,
+            lane,
+        }
+    }
+
+    // builds a grid from newline-terminated rows of equal length, mapping each byte to a cell
+    pub fn from_bytes(
+        s: &[u8],
+        pad: usize,
+        lane: usize,
+        fill: T,
+        mut f: impl FnMut(u8) -> T,
+    ) -> Self {
+        let width = memchr::memchr(b'\n', s).unwrap_or(s.len());
+        let rows = s.split(|&c| c == b'\n').filter(|row| !row.is_empty());
+        let height = rows.clone().count();
+        let mut grid = Self::new(width, height, pad, lane, fill);
+        for (y, row) in rows.enumerate() {
+            let start = grid.pos(0, y as _);
+            for (cell, &c) in grid.cells[start..start + width].iter_mut().zip(row) {
+                *cell = f(c);
+            }
+        }
+        grid
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn pad(&self) -> usize {
+        self.pad
+    }
+
+    #[inline]
+    pub fn stride(&self) -> usize {
+        (2 * self.pad + self.lanes * self.lane).next_multiple_of(self.lane)
+    }
+
+    #[inline]
+    pub fn lanes(&self) -> usize {
+        self.lanes
+    }
+
+    #[inline]
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    #[inline]
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    #[inline]
+    pub fn pos(&self, x: isize, y: isize) -> usize {
+        // flat index of a cell; only meaningful if `contains(x, y)`
+        let pad = self.pad as isize;
+        ((y + pad) * self.stride() as isize + x + pad) as usize
+    }
+
+    #[inline]
+    pub fn offset(&self, dx: isize, dy: isize) -> isize {
+        // flat index difference between a cell and the one at (dx, dy) from it
+        dy * self.stride() as isize + dx
+    }
+
+    #[inline]
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        // whether the cell is in the grid, including padding
+        let (pad, stride) = (self.pad as isize, self.stride() as isize);
+        x >= -pad && x < stride - pad && y >= -pad && y < (self.height + self.pad) as isize
+    }
+
+    #[inline]
+    pub fn get(&self, x: isize, y: isize) -> Option<T> {
+        if self.contains(x, y) {
+            Some(self.cells[self.pos(x, y)])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            let pos = self.pos(x, y);
+            Some(&mut self.cells[pos])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: isize, y: isize, v: T) -> bool {
+        self.get_mut(x, y).map(|cell| *cell = v).is_some()
+    }
+
+    // like the slice helpers, these skip bounds checks unless built with `checked`
+
+    #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn get_at(&self, x: isize, y: isize) -> T {
+        let pos = self.pos(x, y);
+        if cfg!(feature = "checked") {
+            self.cells[pos]
+        } else {
+            unsafe { *self.cells.get_unchecked(pos) }
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn set_at(&mut self, x: isize, y: isize, v: T) {
+        let pos = self.pos(x, y);
+        if cfg!(feature = "checked") {
+            self.cells[pos] = v;
+        } else {
+            unsafe { *self.cells.get_unchecked_mut(pos) = v };
+        }
+    }
+
+    #[inline]
+    pub fn neighbour_offsets(&self) -> [isize; 8] {
+        let mut offsets = [0; 8];
+        for (offset, &(dx, dy)) in offsets.iter_mut().zip(&NEIGHBOURS) {
+            *offset = self.offset(dx, dy);
+        }
+        offsets
+    }
+
+    #[inline]
+    pub fn neighbours(&self, x: isize, y: isize) -> impl Iterator<Item = (isize, isize, T)> + '_ {
+        // all 8 neighbours of a cell that are in the grid, including padding
+        NEIGHBOURS.iter().filter_map(move |&(dx, dy)| {
+            let (x, y) = (x + dx, y + dy);
+            self.get(x, y).map(|v| (x, y, v))
+        })
+    }
+
+    #[inline]
+    pub fn lane_positions(&self) -> impl Iterator<Item = usize> + '_ {
+        // flat indices of the first cell of every lane covering the inside of the grid
+        (0..self.height as isize).flat_map(move |y| {
+            let start = self.pos(0, y);
+            (0..self.lanes).map(move |i| start + i * self.lane)
+        })
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn read_lane<V: Lane<T>>(&self, pos: usize) -> V {
+        // reads `V::LANES` cells starting at a flat index (unaligned)
+        read_lane(&self.cells, pos)
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "checked", track_caller)]
+    pub fn write_lane<V: Lane<T>>(&mut self, pos: usize, v: V) {
+        write_lane(&mut self.cells, pos, v)
+    }
+}
+
+#[test]
+fn test_grid_access() {
+    let grid = Grid::from_bytes(b"#.#\n.##\n", 2, 4, 0u8, |c| (c == b'#') as u8);
+    assert_eq!((grid.width(), grid.height(), grid.stride()), (3, 2, 8));
+    assert_eq!(grid.cells().len(), 8 * 6);
+    assert_eq!(grid.get(0, 0), Some(1));
+    assert_eq!(grid.get(1, 1), Some(1));
+    assert_eq!(grid.get(3, 0), Some(0)); // lane alignment columns read as fill
+    assert_eq!(grid.get(-2, -2), Some(0));
+    assert_eq!(grid.get(-3, 0), None);
+    assert_eq!(grid.get(0, 4), None);
+    let around: u8 = grid.neighbours(1, 0).map(|(_, _, v)| v).sum();
+    assert_eq!(around, 4);
+    let pos = grid.pos(1, 0) as isize;
+    let offsets = grid.neighbour_offsets();
+    let around: u8 = offsets
+        .iter()
+        .map(|&o| grid.cells()[(pos + o) as usize])
+        .sum();
+    assert_eq!(around, 4);
+}
+
+#[test]
+fn test_grid_lanes() {
+    let mut grid = Grid::new(20, 3, 1, 16, 0u8);
+    assert_eq!((grid.stride(), grid.lanes()), (48, 2));
+    assert!(grid.set(19, 2, 6));
+    grid.set_at(19, 2, grid.get_at(19, 2) + 1);
+    let positions: Vec<_> = grid.lane_positions().collect();
+    assert_eq!(positions.len(), 6);
+    for &pos in &positions {
+        let v: u8x16 = grid.read_lane(pos);
+        grid.write_lane(pos, v + u8x16::splat(1));
+    }
+    assert_eq!(grid.get(0, 0), Some(1));
+    assert_eq!(grid.get(19, 2), Some(8));
+    assert_eq!(grid.get(-1, 0), Some(0));
+    assert_eq!(grid.get(0, -1), Some(0));
+    let v: u8x32 = grid.read_lane(grid.pos(0, 1));
+    assert_eq!(v.wrapping_sum(), 32);
+    if cfg!(any(feature = "checked", debug_assertions)) {
+        // a lane running past the end of the cells panics instead of reading past them
+        let end = grid.cells().len() - 15;
+        let read = std::panic::catch_unwind(|| grid.read_lane::<u8x16>(end));
+        assert!(read.is_err());
+    }
+}
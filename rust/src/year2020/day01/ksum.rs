@@ -0,0 +1,478 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+
+// with fewer values than this, sorting them for the two-pointer scan beats hashing them
+const HASH_MIN_LEN: usize = 64;
+
+// the values adding up to the target, in the order they appear in the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KSum {
+    pub values: Vec<i64>,
+    pub indices: Vec<usize>,
+}
+
+impl KSum {
+    #[inline]
+    fn new(values: &[i64], mut indices: Vec<usize>) -> Self {
+        indices.sort_unstable();
+        let values = indices.iter().map(|&i| values[i]).collect();
+        Self { values, indices }
+    }
+
+    #[inline]
+    pub fn product(&self) -> Option<i64> {
+        self.values
+            .iter()
+            .try_fold(1i64, |acc, &x| acc.checked_mul(x))
+    }
+}
+
+#[inline]
+fn find_pair_hashed(values: &[i64], target: i128) -> Option<Vec<usize>> {
+    let mut seen = FxHashMap::with_capacity_and_hasher(values.len(), Default::default());
+    for (j, &x) in values.iter().enumerate() {
+        if let Ok(y) = i64::try_from(target - x as i128) {
+            if let Some(&i) = seen.get(&y) {
+                return Some(vec![i, j]);
+            }
+        }
+        seen.entry(x).or_insert(j);
+    }
+    None
+}
+
+#[inline]
+fn find_sorted(
+    sorted: &[i64],
+    start: usize,
+    k: usize,
+    target: i128,
+    picked: &mut Vec<usize>,
+) -> bool {
+    // positions in `sorted` past `start` of `k >= 2` values adding up to `target`
+    let n = sorted.len();
+    if k == 2 {
+        let (mut lo, mut hi) = (start, n - 1);
+        while lo < hi {
+            match (sorted[lo] as i128 + sorted[hi] as i128).cmp(&target) {
+                Ordering::Less => lo += 1,
+                Ordering::Greater => hi -= 1,
+                Ordering::Equal => {
+                    picked.extend_from_slice(&[lo, hi]);
+                    return true;
+                }
+            }
+        }
+        return false;
+    }
+    let sum = |s: &[i64]| s.iter().map(|&x| x as i128).sum::<i128>();
+    for i in start..=n - k {
+        if i > start && sorted[i] == sorted[i - 1] {
+            continue; // same value as the last one, so the same outcome
+        }
+        let x = sorted[i] as i128;
+        if x + sum(&sorted[i + 1..i + k]) > target {
+            break; // even the smallest values left overshoot, and they only get bigger
+        } else if x + sum(&sorted[n - k + 1..]) < target {
+            continue; // even the largest values left fall short
+        }
+        picked.push(i);
+        if find_sorted(sorted, i + 1, k - 1, target - x, picked) {
+            return true;
+        }
+        picked.pop();
+    }
+    false
+}
+
+// sorts the values, returning them along with the input index of each
+#[inline]
+fn sort_with_indices(values: &[i64]) -> (Vec<i64>, Vec<usize>) {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_unstable_by_key(|&i| values[i]);
+    (order.iter().map(|&i| values[i]).collect(), order)
+}
+
+// any `k` of the values that add up to the target: pairs are looked up in a hash map when
+// there are many values, everything else sorts them first and narrows the last two down
+// with a two-pointer scan
+pub fn find_k_sum(values: &[i64], k: usize, target: i64) -> Option<KSum> {
+    let target = target as i128;
+    let indices = match k {
+        _ if k > values.len() => return None,
+        0 if target == 0 => Vec::new(),
+        0 => return None,
+        1 => vec![values.iter().position(|&x| x as i128 == target)?],
+        2 if values.len() >= HASH_MIN_LEN => find_pair_hashed(values, target)?,
+        _ => {
+            let (sorted, order) = sort_with_indices(values);
+            let mut picked = Vec::with_capacity(k);
+            if !find_sorted(&sorted, 0, k, target, &mut picked) {
+                return None;
+            }
+            picked.into_iter().map(|p| order[p]).collect()
+        }
+    };
+    Some(KSum::new(values, indices))
+}
+
+// which side of the target a near miss may fall on
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Bound {
+    Any,
+    Below,
+    Above,
+}
+
+// the closest sum found so far, as its offset from the target and its positions in `sorted`
+struct Nearest {
+    bound: Bound,
+    diff: Option<i128>,
+    picked: Vec<usize>,
+}
+
+impl Nearest {
+    #[inline]
+    fn offer(
+        &mut self,
+        diff: i128,
+        picked: &[usize],
+        rest: impl IntoIterator<Item = usize>,
+    ) -> bool {
+        // true once there's nothing left to improve on
+        let allowed = match self.bound {
+            Bound::Any => true,
+            Bound::Below => diff <= 0,
+            Bound::Above => diff >= 0,
+        };
+        if allowed && !matches!(self.diff, Some(best) if best.abs() <= diff.abs()) {
+            self.diff = Some(diff);
+            self.picked.clear();
+            self.picked.extend_from_slice(picked);
+            self.picked.extend(rest);
+        }
+        self.diff == Some(0)
+    }
+}
+
+fn nearest_sorted(
+    sorted: &[i64],
+    start: usize,
+    k: usize,
+    target: i128,
+    picked: &mut Vec<usize>,
+    nearest: &mut Nearest,
+) -> bool {
+    // same walk as `find_sorted`, except that the sums it prunes away are still offered, since
+    // the smallest ones overshooting or the largest ones falling short may be the closest yet
+    let n = sorted.len();
+    let sum = |s: &[i64]| s.iter().map(|&x| x as i128).sum::<i128>();
+    if k == 1 {
+        let p = start + sorted[start..].partition_point(|&x| (x as i128) < target);
+        let below = (p > start).then(|| p - 1);
+        for i in below.into_iter().chain(Some(p).filter(|&p| p < n)) {
+            if nearest.offer(sorted[i] as i128 - target, picked, Some(i)) {
+                return true;
+            }
+        }
+        return false;
+    } else if k == 2 {
+        let (mut lo, mut hi) = (start, n - 1);
+        while lo < hi {
+            let diff = sorted[lo] as i128 + sorted[hi] as i128 - target;
+            if nearest.offer(diff, picked, [lo, hi].iter().copied()) {
+                return true;
+            } else if diff < 0 {
+                lo += 1;
+            } else {
+                hi -= 1;
+            }
+        }
+        return false;
+    }
+    for i in start..=n - k {
+        if i > start && sorted[i] == sorted[i - 1] {
+            continue;
+        }
+        let x = sorted[i] as i128;
+        let smallest = x + sum(&sorted[i + 1..i + k]);
+        let largest = x + sum(&sorted[n - k + 1..]);
+        if smallest > target {
+            nearest.offer(smallest - target, picked, i..i + k);
+            break;
+        } else if largest < target {
+            nearest.offer(
+                largest - target,
+                picked,
+                Some(i).into_iter().chain(n - k + 1..n),
+            );
+            continue;
+        }
+        picked.push(i);
+        if nearest_sorted(sorted, i + 1, k - 1, target - x, picked, nearest) {
+            return true;
+        }
+        picked.pop();
+    }
+    false
+}
+
+// the `k` values whose sum is closest to the target (on the given side of it, if bounded),
+// along with how far off that sum is; ties go to whichever is found first
+pub fn nearest_k_sum(values: &[i64], k: usize, target: i64, bound: Bound) -> Option<(KSum, i128)> {
+    if k > values.len() {
+        return None;
+    }
+    // the same pruning as part 2: a value past the target less the `k - 1` smallest ones can only
+    // be in sums that overshoot, and of those, the closest ones only need the `k` smallest such
+    // values (none at all if overshooting isn't allowed)
+    let mut smallest = values.to_vec();
+    if k > 1 {
+        smallest.select_nth_unstable(k - 2);
+    }
+    let below: i128 = smallest[..k.saturating_sub(1)]
+        .iter()
+        .map(|&x| x as i128)
+        .sum();
+    let max = (target as i128 - below).clamp(i64::MIN as i128, i64::MAX as i128) as i64;
+    let keep = if matches!(bound, Bound::Below) { 0 } else { k };
+    let tagged: Vec<_> = values.iter().copied().zip(0..).collect();
+    let pruned: Vec<(i64, usize)> = super::sort_pruned(&tagged, (max, usize::MAX), keep);
+    let (sorted, order): (Vec<_>, Vec<_>) = pruned.into_iter().unzip();
+    if k > sorted.len() {
+        return None; // everything adds up to more than the target
+    }
+    let mut nearest = Nearest {
+        bound,
+        diff: None,
+        picked: Vec::with_capacity(k),
+    };
+    if k == 0 {
+        nearest.offer(-(target as i128), &[], None);
+    } else {
+        nearest_sorted(
+            &sorted,
+            0,
+            k,
+            target as i128,
+            &mut Vec::with_capacity(k),
+            &mut nearest,
+        );
+    }
+    let indices = nearest.picked.iter().map(|&p| order[p]).collect();
+    Some((KSum::new(values, indices), nearest.diff?))
+}
+
+// the distinct values in increasing order, along with the input indices holding each one
+#[inline]
+fn group(values: &[i64]) -> (Vec<i64>, Vec<Vec<usize>>) {
+    let (sorted, order) = sort_with_indices(values);
+    let (mut distinct, mut indices) = (Vec::<i64>::new(), Vec::<Vec<usize>>::new());
+    for (x, i) in sorted.into_iter().zip(order) {
+        match indices.last_mut() {
+            Some(last) if distinct.last() == Some(&x) => last.push(i),
+            _ => {
+                distinct.push(x);
+                indices.push(vec![i]);
+            }
+        }
+    }
+    (distinct, indices)
+}
+
+type Picks = Vec<(usize, usize)>; // how many times each of the distinct values is used
+
+fn for_each_k_sum(
+    distinct: &[i64],
+    counts: &[usize],
+    start: usize,
+    k: usize,
+    target: i128,
+    picks: &mut Picks,
+    f: &mut impl FnMut(&Picks),
+) {
+    // every way to pick `k` values from the distinct ones past `start`, each at most as many
+    // times as it's in the input, so that they add up to `target`
+    if k == 0 {
+        if target == 0 {
+            f(picks);
+        }
+        return;
+    }
+    let k_wide = k as i128;
+    match distinct.last() {
+        Some(&max) if max as i128 * k_wide >= target => {}
+        _ => return, // even the largest value k times falls short
+    }
+    if k == 1 {
+        let rest = &distinct[start.min(distinct.len())..];
+        if let Ok(i) = rest.binary_search_by(|&x| (x as i128).cmp(&target)) {
+            picks.push((start + i, 1));
+            f(picks);
+            picks.pop();
+        }
+        return;
+    }
+    for g in start..distinct.len() {
+        let x = distinct[g] as i128;
+        if x * k_wide > target {
+            break; // everything from here on is at least as large
+        }
+        for times in 1..=k.min(counts[g]) {
+            picks.push((g, times));
+            let rest = target - x * times as i128;
+            for_each_k_sum(distinct, counts, g + 1, k - times, rest, picks, f);
+            picks.pop();
+        }
+    }
+}
+
+#[inline]
+fn expand(
+    indices: &[Vec<usize>],
+    picks: &[(usize, usize)],
+    out: &mut Vec<usize>,
+    f: &mut impl FnMut(&[usize]),
+) {
+    // every choice of input entries for the picked values
+    match picks.split_first() {
+        None => f(out),
+        Some((&(g, times), rest)) => {
+            for chosen in indices[g].iter().copied().combinations(times) {
+                out.extend(chosen);
+                expand(indices, rest, out, f);
+                out.truncate(out.len() - times);
+            }
+        }
+    }
+}
+
+// all `k` entries adding up to the target; entries with equal values are still told apart,
+// so e.g. two 1010s and a third one make three pairs
+pub fn all_k_sums(values: &[i64], k: usize, target: i64) -> impl Iterator<Item = KSum> + '_ {
+    let (distinct, indices) = group(values);
+    let counts: Vec<_> = indices.iter().map(Vec::len).collect();
+    let mut found = Vec::new();
+    let mut collect = |picks: &Picks| found.push(picks.clone());
+    for_each_k_sum(
+        &distinct,
+        &counts,
+        0,
+        k,
+        target as i128,
+        &mut Vec::new(),
+        &mut collect,
+    );
+    found.into_iter().flat_map(move |picks| {
+        let mut sums = Vec::new();
+        let mut push = |chosen: &[usize]| sums.push(KSum::new(values, chosen.to_vec()));
+        expand(&indices, &picks, &mut Vec::with_capacity(k), &mut push);
+        sums
+    })
+}
+
+// how many items `all_k_sums` would yield, without listing them
+pub fn count_k_sums(values: &[i64], k: usize, target: i64) -> u64 {
+    let binomial =
+        |n: usize, r: usize| (0..r).fold(1u64, |acc, i| acc * (n - i) as u64 / (i + 1) as u64);
+    let (distinct, indices) = group(values);
+    let counts: Vec<_> = indices.iter().map(Vec::len).collect();
+    let mut total = 0;
+    let mut count = |picks: &Picks| {
+        total += picks
+            .iter()
+            .map(|&(g, times)| binomial(counts[g], times))
+            .product::<u64>();
+    };
+    for_each_k_sum(
+        &distinct,
+        &counts,
+        0,
+        k,
+        target as i128,
+        &mut Vec::new(),
+        &mut count,
+    );
+    total
+}
+
+#[test]
+fn test_day01_find_k_sum() {
+    let s = [1721, 979, 366, 299, 675, 1456];
+    let pair = find_k_sum(&s, 2, 2020).unwrap();
+    assert_eq!((pair.values, pair.indices), (vec![1721, 299], vec![0, 3]));
+    let triple = find_k_sum(&s, 3, 2020).unwrap();
+    assert_eq!(triple.indices, vec![1, 2, 4]);
+    assert_eq!(triple.product(), Some(241861950));
+    assert_eq!(find_k_sum(&s, 4, 3741).unwrap().indices, vec![0, 1, 2, 4]);
+    assert_eq!(find_k_sum(&s, 1, 366).unwrap().indices, vec![2]);
+    assert_eq!(find_k_sum(&s, 0, 0).unwrap().indices, vec![]);
+    assert_eq!(find_k_sum(&s, 2, 2021), None);
+    assert_eq!(find_k_sum(&s, 7, 0), None);
+    assert_eq!(find_k_sum(&[1010, 5], 2, 2020), None); // a value can't pair with itself
+    let big = [i64::MAX, i64::MAX, -1];
+    let pair = find_k_sum(&big, 2, i64::MAX - 1).unwrap();
+    assert_eq!(pair.values, vec![i64::MAX, -1]); // no overflow on the way
+    assert_eq!(pair.product(), Some(-i64::MAX));
+}
+
+#[test]
+fn test_day01_find_k_sum_hashed() {
+    let s = super::parse(super::input());
+    assert!(s.len() >= HASH_MIN_LEN);
+    assert_eq!(find_k_sum(&s, 2, 2020).unwrap().product(), Some(974304));
+    assert_eq!(find_k_sum(&s, 3, 2020).unwrap().product(), Some(236430480));
+    let (hashed, sorted) = (find_pair_hashed(&s, 2020), sort_with_indices(&s));
+    let mut picked = Vec::new();
+    assert!(find_sorted(&sorted.0, 0, 2, 2020, &mut picked));
+    let mut picked: Vec<_> = picked.into_iter().map(|p| sorted.1[p]).collect();
+    picked.sort_unstable();
+    assert_eq!(hashed, Some(picked));
+}
+
+#[test]
+fn test_day01_all_k_sums() {
+    let indices = |s, k, target| {
+        let sums = all_k_sums(s, k, target).map(|sum| sum.indices);
+        sums.sorted().collect::<Vec<_>>()
+    };
+    let s = [1010, 1010, 1010, 5, 2015, 2015];
+    let pairs = vec![vec![0, 1], vec![0, 2], vec![1, 2], vec![3, 4], vec![3, 5]];
+    assert_eq!(indices(&s, 2, 2020), pairs);
+    assert_eq!(count_k_sums(&s, 2, 2020), 5);
+    let s = [10, 10, 2000, 2000, 1010, 1000];
+    let triples = vec![vec![0, 1, 2], vec![0, 1, 3], vec![0, 4, 5], vec![1, 4, 5]];
+    assert_eq!(indices(&s, 3, 2020), triples);
+    assert_eq!(count_k_sums(&s, 3, 2020), 4);
+    assert_eq!(count_k_sums(&s, 3, 2021), 0);
+    assert_eq!(indices(&s, 0, 0), vec![Vec::<usize>::new()]);
+    let s = super::parse(super::input());
+    assert_eq!(count_k_sums(&s, 2, 2020), 1);
+    assert_eq!(count_k_sums(&s, 3, 2020), 1);
+    let triple = all_k_sums(&s, 3, 2020).next().unwrap();
+    assert_eq!(Some(triple), find_k_sum(&s, 3, 2020));
+}
+
+#[test]
+fn test_day01_nearest_k_sum() {
+    let nearest = |s, k, target, bound| {
+        let (sum, diff) = nearest_k_sum(s, k, target, bound)?;
+        Some((sum.indices, diff))
+    };
+    let s = [1721, 979, 366, 299, 675, 1456];
+    assert_eq!(nearest(&s, 2, 2020, Bound::Any), Some((vec![0, 3], 0)));
+    assert_eq!(nearest(&s, 2, 2021, Bound::Any), Some((vec![0, 3], -1)));
+    assert_eq!(nearest(&s, 2, 2021, Bound::Above), Some((vec![0, 2], 66)));
+    assert_eq!(nearest(&s, 3, 1, Bound::Above), Some((vec![2, 3, 4], 1339)));
+    assert_eq!(nearest(&s, 3, 1, Bound::Below), None);
+    assert_eq!(nearest(&s, 1, 1000, Bound::Any), Some((vec![1], -21)));
+    assert_eq!(nearest(&s, 1, 1000, Bound::Above), Some((vec![5], 456)));
+    assert_eq!(nearest(&s, 0, 5, Bound::Below), Some((vec![], -5)));
+    assert_eq!(nearest(&s, 7, 0, Bound::Any), None);
+    let s = super::parse(super::input());
+    let (triple, diff) = nearest_k_sum(&s, 3, 2020, Bound::Any).unwrap();
+    assert_eq!((triple.product(), diff), (Some(236430480), 0));
+}
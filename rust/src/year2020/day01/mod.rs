@@ -0,0 +1,481 @@
+use std::io::{self, BufRead};
+use std::iter;
+use std::ops::DerefMut;
+
+use arrayvec::ArrayVec;
+use memchr::memchr;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::params::{self, parse_value, ParamError};
+use crate::registry::Strategy;
+use crate::utils::*;
+
+mod ksum;
+
+pub use self::ksum::{all_k_sums, count_k_sums, find_k_sum, nearest_k_sum, Bound, KSum};
+
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub target: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { target: 2020 }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        match key {
+            "target" => self.target = parse_value(key, value)?,
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![("target", self.target.to_string())]
+    }
+}
+
+#[inline]
+fn find_sum_min2(s: &[i16]) -> i16 {
+    let (mut a, mut b) = (i16::MAX, i16::MAX);
+    for x in s {
+        if *x >= b {
+            // most likely
+            continue;
+        } else if *x >= a {
+            // less likely
+            b = *x;
+        } else {
+            // least likely
+            b = a;
+            a = *x;
+        }
+    }
+    a.saturating_add(b) // no second value to pair with if it's still at the max
+}
+
+// the lookup tables are sized for reports like the puzzle's: at most 256 entries, all of them
+// (and the target) below 4096; anything else goes through the general k-sum search
+const MAX_SMALL_LEN: usize = 256;
+const TABLE_LEN: usize = 4096;
+
+type Small = ArrayVec<[i16; MAX_SMALL_LEN]>;
+
+#[inline]
+fn fits_table(x: i64) -> bool {
+    (0..TABLE_LEN as i64).contains(&x)
+}
+
+#[inline]
+fn as_small(s: &[i64], target: i64) -> Option<Small> {
+    if s.len() > MAX_SMALL_LEN || !fits_table(target) {
+        return None;
+    }
+    s.iter()
+        .map(|&x| Some(x as i16).filter(|_| fits_table(x)))
+        .collect()
+}
+
+#[inline]
+fn parse_entry(s: &mut &[u8]) -> Option<i64> {
+    // none if the line isn't a number or doesn't fit; the whole line is consumed either way, so
+    // the next one is still read from its start
+    let neg = s.first() == Some(&b'-');
+    *s = s.advance(neg as usize);
+    let n = s
+        .iter()
+        .position(|c| !c.is_ascii_digit())
+        .unwrap_or(s.len());
+    let (digits, rest) = s.split_at(n);
+    let end = memchr(b'\n', rest).map_or(rest.len(), |i| i + 1);
+    *s = rest.advance(end);
+    if digits.is_empty() || end > 1 {
+        return None; // no digits, or something other than the newline after them
+    }
+    // accumulated as a negative number, since i64::MIN has no positive counterpart
+    let x = digits.iter().try_fold(0i64, |x, &d| {
+        x.checked_mul(10)?.checked_sub((d - b'0') as i64)
+    })?;
+    if neg {
+        Some(x)
+    } else {
+        x.checked_neg()
+    }
+}
+
+pub type Parsed = Vec<i64>;
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed {
+    let mut vec = Vec::with_capacity(s.len() / 5);
+    while s.len() > 1 {
+        match parse_entry(&mut s) {
+            Some(x) => vec.push(x),
+            None => panic!("entry {} is not a 64-bit integer", vec.len() + 1),
+        }
+    }
+    vec
+}
+
+#[inline]
+fn read_entries<R: BufRead>(r: R, mut f: impl FnMut(i64)) -> io::Result<()> {
+    let (mut entry, mut invalid) = (0, None);
+    for_each_line(r, |mut line| {
+        entry += 1;
+        match parse_entry(&mut line) {
+            Some(x) => f(x),
+            None => invalid = invalid.or(Some(entry)),
+        }
+    })?;
+    match invalid {
+        Some(entry) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("entry {} is not a 64-bit integer", entry),
+        )),
+        None => Ok(()),
+    }
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    to_lines(s)
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+fn match_pair(arr: &mut [i16; TABLE_LEN], x: i16, target: i16) -> Option<i64> {
+    let x = x.min(target) as usize;
+    let rem = target as usize - x;
+    let y = arr.get_at(x); // looked up first, so that an entry can't pair with itself
+    arr.set_at(rem, x as _);
+    if y != 0 {
+        Some((x as i64) * (y as i64))
+    } else {
+        None
+    }
+}
+
+#[inline]
+fn k_sum_product(s: &[i64], k: usize, target: i64) -> i64 {
+    // 0 if there's no match, same as when its product doesn't fit
+    let found = find_k_sum(s, k, target);
+    found.and_then(|found| found.product()).unwrap_or(0)
+}
+
+#[inline]
+fn solve1_small(s: &Small, target: i16) -> i64 {
+    let mut arr = [0i16; TABLE_LEN];
+    for &x in s {
+        if let Some(answer) = match_pair(&mut arr, x, target) {
+            return answer;
+        }
+    }
+    0
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> i64 {
+    solve1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve1_with(s: &Parsed, params: &Params) -> i64 {
+    match as_small(s, params.target) {
+        Some(small) => solve1_small(&small, params.target as _),
+        None => k_sum_product(s, 2, params.target),
+    }
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> i64 {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> i64 {
+    solve1_with(&parse(s), params)
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<i64> {
+    // entries in 0..=2020 go into a fixed table; the others can only pair with each other, so
+    // only they need a set, which stays empty for reports like the puzzle's
+    let mut seen = [false; 2021];
+    let mut others = FxHashSet::default();
+    let mut answer = None;
+    read_entries(r, |x| {
+        if answer.is_some() {
+            return;
+        }
+        let found = match 2020i64.checked_sub(x) {
+            Some(y) if (0..=2020).contains(&y) => seen[y as usize],
+            Some(y) => others.contains(&y),
+            None => false,
+        };
+        if found {
+            answer = Some(x.checked_mul(2020 - x).unwrap_or(0));
+        } else if (0..=2020).contains(&x) {
+            seen[x as usize] = true;
+        } else {
+            others.insert(x);
+        }
+    })?;
+    Ok(answer.unwrap_or(0))
+}
+
+#[inline]
+fn sort_pruned<T, C>(s: &[T], max: T, keep: usize) -> C
+where
+    T: Copy + Ord,
+    C: Default + Extend<T> + DerefMut<Target = [T]>,
+{
+    // the values up to `max` in increasing order, plus the `keep` smallest of the ones past it;
+    // the callers pick `max` so that anything larger can't be part of a sum that isn't too large
+    let mut arr = C::default();
+    arr.extend(s.iter().copied().filter(|&x| x <= max));
+    if keep != 0 {
+        let mut over: Vec<T> = s.iter().copied().filter(|&x| x > max).collect();
+        if keep < over.len() {
+            over.select_nth_unstable(keep);
+        }
+        arr.extend(over.into_iter().take(keep));
+    }
+    quickersort::sort(&mut arr);
+    arr
+}
+
+#[inline]
+fn solve2_small(s: &Small, target: i16) -> i64 {
+    let arr: Small = sort_pruned(s, target - find_sum_min2(s), 0);
+    let n = arr.len();
+
+    for i in 0..n.saturating_sub(2) {
+        let a_i = arr.get_at(i);
+        let ai_rem = a_i - target;
+        for j in i + 1..n - 1 {
+            let a_j = arr.get_at(j);
+            let a_i_j = ai_rem + a_j;
+            for k in j + 1..n {
+                let a_k = arr.get_at(k);
+                let a_i_j_k = a_i_j + a_k;
+                if a_i_j_k < 0 {
+                    continue;
+                } else if a_i_j_k > 0 {
+                    break;
+                } else {
+                    return (a_i as i64) * (a_j as i64) * (a_k as i64);
+                }
+            }
+        }
+    }
+    0
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> i64 {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve2_with(s: &Parsed, params: &Params) -> i64 {
+    match as_small(s, params.target) {
+        Some(small) => solve2_small(&small, params.target as _),
+        None => k_sum_product(s, 3, params.target),
+    }
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> i64 {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> i64 {
+    solve2_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (i64, i64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<i64> {
+    // with no negative values, only the ones up to 2020 can take part in a triple, so it's
+    // enough to count them; the rest are kept in case a negative one turns up later, since any of
+    // them could then complete a triple: memory is only bounded for reports without entries
+    // outside 0..=2020, and otherwise grows with the number of distinct ones
+    let mut counts = [0u8; 2021];
+    let mut others = FxHashMap::<i64, u8>::default();
+    let mut has_negative = false;
+    read_entries(r, |x| match x {
+        0..=2020 => counts[x as usize] = counts[x as usize].saturating_add(1),
+        _ => {
+            has_negative |= x < 0;
+            let count = others.entry(x).or_default();
+            *count = count.saturating_add(1);
+        }
+    })?;
+    if has_negative {
+        // a triple uses each value at most three times
+        let counts = counts.iter().enumerate().map(|(x, &n)| (x as i64, n));
+        let entries = others.into_iter().chain(counts);
+        let entries: Vec<_> = entries
+            .flat_map(|(x, n)| iter::repeat_n(x, n.min(3) as usize))
+            .collect();
+        return Ok(k_sum_product(&entries, 3, 2020));
+    }
+    for a in 0..=2020 / 3 {
+        if counts[a] == 0 {
+            continue;
+        }
+        for b in a..=(2020 - a) / 2 {
+            let c = 2020 - a - b;
+            let needed_b = 1 + (b == a) as u8;
+            let needed_c = 1 + (c == a) as u8 + (c == b) as u8;
+            if counts[b] >= needed_b && counts[c] >= needed_c {
+                return Ok((a as i64) * (b as i64) * (c as i64));
+            }
+        }
+    }
+    Ok(0)
+}
+
+pub static STRATEGIES: &[Strategy] = &[
+    Strategy {
+        name: "lookup",
+        part: 1,
+        solver: |s| part1(s).to_string(),
+    },
+    Strategy {
+        name: "k-sum",
+        part: 1,
+        solver: |s| k_sum_product(&parse(s), 2, 2020).to_string(),
+    },
+    Strategy {
+        name: "sorted",
+        part: 2,
+        solver: |s| part2(s).to_string(),
+    },
+    Strategy {
+        name: "k-sum",
+        part: 2,
+        solver: |s| k_sum_product(&parse(s), 3, 2020).to_string(),
+    },
+];
+
+#[test]
+fn test_day01_part1() {
+    assert_eq!(part1(input()), 974304);
+}
+
+#[test]
+fn test_day01_part2() {
+    assert_eq!(part2(input()), 236430480);
+}
+
+#[test]
+fn test_day01_solve_both() {
+    assert_eq!(solve_both(input()), (974304, 236430480));
+}
+
+#[test]
+fn test_day01_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day01_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        974304
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        236430480
+    );
+}
+
+#[test]
+fn test_day01_params() {
+    let s = b"1721\n979\n366\n299\n675\n1456\n";
+    assert_eq!(part1_with(s, &Params::default()), 514579);
+    assert_eq!(part1_with(s, &Params { target: 1041 }), 247050);
+    assert_eq!(part1_with(s, &Params { target: 2021 }), 0);
+    assert_eq!(part2_with(s, &Params { target: 1340 }), 73867950);
+    assert_eq!(part2_with(s, &Params { target: 100 }), 0);
+}
+
+#[test]
+fn test_day01_large() {
+    let mut s: String = (3000..3300).map(|x| format!("{}\n", x)).collect();
+    s.push_str("700\n900\n420\n");
+    assert_eq!(part1(s.as_bytes()), 0);
+    assert_eq!(part2(s.as_bytes()), 264600000);
+    let s = b"3000000000\n1\n-2999997980\n";
+    assert_eq!(part1(s), -8999993940000000000);
+    assert_eq!(part1_reader(&s[..]).unwrap(), -8999993940000000000);
+    let s2 = b"-5\n1000\n1025\n1000\n5000\n"; // past the count table, and negative
+    assert_eq!(part2_reader(&s2[..]).unwrap(), -5125000);
+    // repeats past the table are only kept once, and still found before a later negative
+    let s3 = "3000\n".repeat(1000) + "20\n-1000\n";
+    assert_eq!(part2_reader(s3.as_bytes()).unwrap(), -60000000);
+    assert_eq!(part2(s), 0);
+    assert_eq!(
+        part2_with(s, &Params { target: 2021 }),
+        -8999993940000000000
+    );
+}
+
+#[test]
+fn test_day01_self_pair() {
+    // half the target only pairs up if it's in the report twice, whichever path is taken
+    let s = b"1010\n5\n";
+    assert_eq!(part1(s), 0);
+    assert_eq!(k_sum_product(&parse(s), 2, 2020), 0);
+    assert_eq!(part1_reader(&s[..]).unwrap(), 0);
+    let s = b"1010\n5\n1010\n";
+    assert_eq!(part1(s), 1020100);
+    assert_eq!(k_sum_product(&parse(s), 2, 2020), 1020100);
+    assert_eq!(part1_reader(&s[..]).unwrap(), 1020100);
+}
+
+#[test]
+fn test_day01_entry_range() {
+    let s = b"9223372036854775807\n-9223372036854775808\n-1\n";
+    assert_eq!(parse(s), vec![i64::MAX, i64::MIN, -1]);
+    assert_eq!(
+        part1_with(
+            s,
+            &Params {
+                target: i64::MAX - 1
+            }
+        ),
+        -i64::MAX
+    );
+    for bad in &[
+        "9223372036854775808\n",
+        "-9223372036854775809\n",
+        "12345678901234567890\n",
+    ] {
+        let s = format!("1\n{}2019\n", bad);
+        let err = part1_reader(s.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "entry 2 is not a 64-bit integer");
+        assert!(std::panic::catch_unwind(|| parse(s.as_bytes())).is_err());
+    }
+    let mut s = &b"123456789012345678901\n2019\n"[..]; // the next line isn't thrown off
+    assert_eq!(
+        (parse_entry(&mut s), parse_entry(&mut s)),
+        (None, Some(2019))
+    );
+}
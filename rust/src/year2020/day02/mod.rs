@@ -0,0 +1,184 @@
+use std::fmt;
+use std::io::{self, BufRead};
+
+use bstr::ByteSlice;
+
+use crate::registry::Strategy;
+use crate::utils::*;
+
+mod policy;
+pub mod simd;
+
+pub use self::policy::{audit, repairs, CountRange, Edit, PasswordPolicy, Positional};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Password<'a> {
+    pub pass: &'a [u8],
+    pub n1: u32,
+    pub n2: u32,
+    pub char: u8,
+}
+
+impl<'a> Password<'a> {
+    #[inline]
+    pub fn parse(s: &mut &'a [u8]) -> Self {
+        let n1 = parse_int_fast(s, 1, 9);
+        let n2 = parse_int_fast(s, 1, 9);
+        let char = s.get_first();
+        *s = s.advance(3);
+        let i = s.memchr(b'\n');
+        let pass = &s[..i];
+        *s = s.advance(i + 1);
+        Self { pass, n1, n2, char }
+    }
+}
+
+impl fmt::Display for Password<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (n1, n2, char, pass) = (self.n1, self.n2, self.char as char, self.pass.as_bstr());
+        write!(f, "{}-{} {}: {}", n1, n2, char, pass)
+    }
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+pub type Parsed<'a> = Vec<Password<'a>>;
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed<'_> {
+    let mut passwords = Vec::with_capacity(s.len() / 16);
+    while s.len() > 1 {
+        passwords.push(Password::parse(&mut s));
+    }
+    passwords
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    to_lines(s)
+}
+
+#[inline]
+pub fn count_valid(mut s: &[u8], policy: &impl PasswordPolicy) -> u32 {
+    let mut valid = 0;
+    while s.len() > 1 {
+        let p = Password::parse(&mut s);
+        valid += policy.is_valid(&p) as u32;
+    }
+    valid
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u32 {
+    s.iter().map(|p| CountRange.is_valid(p) as u32).sum()
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u32 {
+    count_valid(s, &CountRange)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u32 {
+    s.iter().map(|p| Positional.is_valid(p) as u32).sum()
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u32 {
+    count_valid(s, &Positional)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u32, u32) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+fn count_valid_reader<R: BufRead>(r: R, policy: impl PasswordPolicy) -> io::Result<u64> {
+    let mut valid = 0;
+    for_each_line(r, |mut line| {
+        valid += policy.is_valid(&Password::parse(&mut line)) as u64
+    })?;
+    Ok(valid)
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    count_valid_reader(r, CountRange)
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    count_valid_reader(r, Positional)
+}
+
+pub static STRATEGIES: &[Strategy] = &[
+    Strategy {
+        name: "scalar",
+        part: 1,
+        solver: |s| part1(s).to_string(),
+    },
+    Strategy {
+        name: "simd",
+        part: 1,
+        solver: |s| simd::part1(s).to_string(),
+    },
+    Strategy {
+        name: "scalar",
+        part: 2,
+        solver: |s| part2(s).to_string(),
+    },
+    Strategy {
+        name: "simd",
+        part: 2,
+        solver: |s| simd::part2(s).to_string(),
+    },
+];
+
+#[test]
+fn test_day02_part1() {
+    assert_eq!(part1(input()), 477);
+}
+
+#[test]
+fn test_day02_part2() {
+    assert_eq!(part2(input()), 686);
+}
+
+#[test]
+fn test_day02_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day02_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        477
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        686
+    );
+}
+
+#[test]
+fn test_day02_large() {
+    let long = "a".repeat(150);
+    let s = format!(
+        "33-40 a: {}\n100-200 a: {}\n0-2 a: ba\n1-50 a: abc\n",
+        &long[..40],
+        long
+    );
+    let s = s.as_bytes();
+    assert_eq!(parse(s)[1].n2, 200);
+    assert_eq!(part1(s), 4);
+    assert_eq!(part2(s), 3); // positions 0 and past the end hold no letter
+    assert_eq!(part1_reader(s).unwrap(), 4);
+    assert_eq!(part2_reader(s).unwrap(), 3);
+}
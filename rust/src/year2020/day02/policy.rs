@@ -0,0 +1,300 @@
+use std::iter;
+
+use super::Password;
+
+// a rule every password on a line has to follow, given the numbers and the letter in front of it
+pub trait PasswordPolicy {
+    fn is_valid(&self, p: &Password) -> bool;
+
+    // why the password does or doesn't follow the rule, in a few words
+    fn reason(&self, p: &Password) -> String;
+
+    // the smallest edit that makes a failing password follow the rule; none if it already does,
+    // if no edit would help or if the policy can't tell
+    fn repair(&self, _: &Password) -> Option<Edit> {
+        None
+    }
+}
+
+// a change to a password, with 1-based positions like in the policies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    // append this many more of the letter
+    Add(usize),
+    // drop the letters at these positions
+    Remove(Vec<usize>),
+    // put the letter at this position
+    Set(usize),
+    // put some other character at this position
+    Clear(usize),
+    // pad with other characters and put the letter at this position, past the end
+    Extend(usize),
+}
+
+#[inline]
+fn other(c: u8) -> u8 {
+    // any character that isn't the letter
+    if c == b'a' {
+        b'b'
+    } else {
+        b'a'
+    }
+}
+
+impl Edit {
+    // the password with the edit made
+    pub fn apply(&self, p: &Password) -> Vec<u8> {
+        let mut pass = p.pass.to_vec();
+        match self {
+            Edit::Add(n) => pass.resize(pass.len() + n, p.char),
+            Edit::Remove(positions) => {
+                for &i in positions.iter().rev() {
+                    pass.remove(i - 1);
+                }
+            }
+            Edit::Set(i) => pass[i - 1] = p.char,
+            Edit::Clear(i) => pass[i - 1] = other(p.char),
+            Edit::Extend(i) => {
+                pass.resize(i - 1, other(p.char));
+                pass.push(p.char);
+            }
+        }
+        pass
+    }
+}
+
+// the letter has to occur between n1 and n2 times (part 1)
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CountRange;
+
+impl CountRange {
+    #[inline]
+    fn count(p: &Password) -> usize {
+        p.pass.iter().filter(|&&c| c == p.char).count()
+    }
+}
+
+impl PasswordPolicy for CountRange {
+    #[inline]
+    fn is_valid(&self, p: &Password) -> bool {
+        let n = Self::count(p);
+        n >= p.n1 as usize && n <= p.n2 as usize
+    }
+
+    fn reason(&self, p: &Password) -> String {
+        let n = Self::count(p);
+        let side = if n < p.n1 as usize {
+            "too few"
+        } else if n > p.n2 as usize {
+            "too many"
+        } else {
+            "within range"
+        };
+        let (c, n1, n2) = (p.char as char, p.n1, p.n2);
+        format!("{} {:?}s, {}-{} allowed ({})", n, c, n1, n2, side)
+    }
+
+    fn repair(&self, p: &Password) -> Option<Edit> {
+        // add the missing letters, or drop the extra ones from the end
+        let (n, n1, n2) = (Self::count(p), p.n1 as usize, p.n2 as usize);
+        if n1 > n2 {
+            None
+        } else if n < n1 {
+            Some(Edit::Add(n1 - n))
+        } else if n > n2 {
+            let positions = p.pass.iter().enumerate().filter(|&(_, &c)| c == p.char);
+            let positions = positions.map(|(i, _)| i + 1).skip(n2).collect();
+            Some(Edit::Remove(positions))
+        } else {
+            None
+        }
+    }
+}
+
+// the letter has to be at exactly one of the 1-based positions n1 and n2 (part 2); there's
+// no letter at position 0 or past the end of the password
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Positional;
+
+impl Positional {
+    #[inline]
+    fn at(p: &Password, n: u32) -> Option<u8> {
+        let i = (n as usize).checked_sub(1)?;
+        p.pass.get(i).copied()
+    }
+
+    #[inline]
+    fn matches(p: &Password) -> (bool, bool) {
+        let match1 = Self::at(p, p.n1) == Some(p.char);
+        let match2 = Self::at(p, p.n2) == Some(p.char);
+        (match1, match2)
+    }
+}
+
+impl PasswordPolicy for Positional {
+    #[inline]
+    fn is_valid(&self, p: &Password) -> bool {
+        let (match1, match2) = Self::matches(p);
+        match1 != match2
+    }
+
+    fn reason(&self, p: &Password) -> String {
+        let (c, n1, n2) = (p.char as char, p.n1, p.n2);
+        let mut reason = match Self::matches(p) {
+            (true, true) => format!("{:?} at both {} and {}", c, n1, n2),
+            (false, false) => format!("{:?} at neither {} nor {}", c, n1, n2),
+            (true, false) => format!("{:?} at {} only", c, n1),
+            (false, true) => format!("{:?} at {} only", c, n2),
+        };
+        for &n in &[n1, n2] {
+            if Self::at(p, n).is_none() {
+                reason += &format!(", no position {}", n);
+            }
+        }
+        reason
+    }
+
+    fn repair(&self, p: &Password) -> Option<Edit> {
+        // a single change always does unless both positions are the same; if neither exists,
+        // the password is extended up to the nearest one (there's never a position 0)
+        let exists = |n: u32| Self::at(p, n).is_some();
+        let nearest = match (p.n1, p.n2) {
+            (0, n) | (n, 0) => n,
+            (n1, n2) => n1.min(n2),
+        };
+        match Self::matches(p) {
+            _ if p.n1 == p.n2 => None,
+            (true, true) => Some(Edit::Clear(p.n2 as usize)),
+            (false, false) if exists(p.n1) => Some(Edit::Set(p.n1 as usize)),
+            (false, false) if exists(p.n2) => Some(Edit::Set(p.n2 as usize)),
+            (false, false) => Some(Edit::Extend(nearest as usize)),
+            _ => None,
+        }
+    }
+}
+
+// every password in the input with its 1-based line number, whether it follows the policy and why
+pub fn audit<'a, P: PasswordPolicy + ?Sized>(
+    mut s: &'a [u8],
+    policy: &'a P,
+) -> impl Iterator<Item = (usize, Password<'a>, bool, String)> + 'a {
+    let mut line = 0;
+    iter::from_fn(move || {
+        if s.len() <= 1 {
+            return None;
+        }
+        line += 1;
+        let p = Password::parse(&mut s);
+        let (valid, reason) = (policy.is_valid(&p), policy.reason(&p));
+        Some((line, p, valid, reason))
+    })
+}
+
+// every password in the input that doesn't follow the policy, with its 1-based line number and
+// the smallest edit that fixes it, if there's one
+pub fn repairs<'a, P: PasswordPolicy + ?Sized>(
+    s: &'a [u8],
+    policy: &'a P,
+) -> impl Iterator<Item = (usize, Password<'a>, Option<Edit>)> + 'a {
+    let failing = audit(s, policy).filter(|&(_, _, valid, _)| !valid);
+    failing.map(move |(line, p, _, _)| {
+        let edit = policy.repair(&p);
+        (line, p, edit)
+    })
+}
+
+#[test]
+fn test_day02_audit() {
+    let s = b"1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+    let audit1: Vec<_> = audit(s, &CountRange)
+        .map(|(line, _, valid, reason)| (line, valid, reason))
+        .collect();
+    assert_eq!(
+        audit1,
+        vec![
+            (1, true, "1 'a's, 1-3 allowed (within range)".to_owned()),
+            (2, false, "0 'b's, 1-3 allowed (too few)".to_owned()),
+            (3, true, "9 'c's, 2-9 allowed (within range)".to_owned()),
+        ]
+    );
+    let audit2: Vec<_> = audit(s, &Positional)
+        .map(|(line, p, valid, reason)| (line, p.pass, valid, reason))
+        .collect();
+    assert_eq!(
+        audit2,
+        vec![
+            (1, &b"abcde"[..], true, "'a' at 1 only".to_owned()),
+            (2, &b"cdefg"[..], false, "'b' at neither 1 nor 3".to_owned()),
+            (
+                3,
+                &b"ccccccccc"[..],
+                false,
+                "'c' at both 2 and 9".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_day02_custom_policy() {
+    // e.g. a stricter rule than part 1's: the letter at most n1 times
+    struct AtMost;
+    impl PasswordPolicy for AtMost {
+        fn is_valid(&self, p: &Password) -> bool {
+            p.pass.iter().filter(|&&c| c == p.char).count() <= p.n1 as usize
+        }
+        fn reason(&self, _: &Password) -> String {
+            String::new()
+        }
+    }
+    let s = b"1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+    let reason = Positional.reason(&Password::parse(&mut &b"0-9 a: abc\n"[..]));
+    assert_eq!(
+        reason,
+        "'a' at neither 0 nor 9, no position 0, no position 9"
+    );
+    let failing = audit(s, &AtMost).filter(|&(_, _, valid, _)| !valid);
+    assert_eq!(failing.map(|(line, ..)| line).collect::<Vec<_>>(), vec![3]);
+    assert_eq!(super::count_valid(s, &AtMost), 2);
+    assert_eq!(super::count_valid(super::input(), &CountRange), 477);
+}
+
+#[test]
+fn test_day02_repair() {
+    let s = b"1-3 a: abcde\n1-3 b: cdefg\n2-3 c: ccccccccc\n2-2 a: aba\n9-10 a: abc\n0-5 b: b\n";
+    let edits = |policy| -> Vec<_> {
+        let repairs = repairs(s, policy).map(|(line, _, edit)| (line, edit));
+        repairs.collect()
+    };
+    let count_range = vec![
+        (2, Some(Edit::Add(1))),
+        (3, Some(Edit::Remove(vec![4, 5, 6, 7, 8, 9]))),
+        (5, Some(Edit::Add(8))),
+    ];
+    assert_eq!(edits(&CountRange as &dyn PasswordPolicy), count_range);
+    let positional = vec![
+        (2, Some(Edit::Set(1))),
+        (3, Some(Edit::Clear(3))),
+        (4, None), // the same position twice can't ever hold the letter just once
+        (5, Some(Edit::Extend(9))), // neither position exists, pad up to the nearer one
+        (6, Some(Edit::Extend(5))),
+    ];
+    assert_eq!(edits(&Positional), positional);
+    let p = Password::parse(&mut &b"9-10 a: abc\n"[..]);
+    assert_eq!(Edit::Extend(9).apply(&p), b"abcbbbbba");
+    // every repair leaves a password the policy accepts
+    let policies: [&dyn PasswordPolicy; 2] = [&CountRange, &Positional];
+    for policy in &policies {
+        let fixable = repairs(s, *policy).filter(|(_, _, edit)| edit.is_some());
+        for (_, p, edit) in repairs(super::input(), *policy).chain(fixable) {
+            let pass = edit.unwrap().apply(&p);
+            let fixed = Password { pass: &pass, ..p };
+            assert!(
+                policy.is_valid(&fixed),
+                "{} -> {:?}",
+                p,
+                String::from_utf8_lossy(&pass)
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::arch::x86_64::{__m256i, _mm256_movemask_epi8};
+use std::mem;
+
+use memchr::memchr;
+use packed_simd_2::{m8x32, u8x32};
+
+use crate::utils::*;
+
+// each line is loaded as one vector along with whatever follows it: comparing that against
+// newlines, colons, dashes, spaces and digits finds the end of the line (and so the start of the
+// next one) and checks and splits up its header, and comparing it against the letter gives a mask
+// of the password whose popcount is the count for the first policy and whose bits at the two
+// positions are all the second policy needs; lines longer than a vector or too close to the end
+// of the input to load one are parsed on their own, and lines that don't parse follow neither
+// policy
+
+const LANES: usize = 32;
+
+#[inline]
+fn bitmask(m: m8x32) -> u32 {
+    // one bit per lane; `m8x32::bitmask` doesn't compile down to a single movemask and costs
+    // more than everything else here put together
+    unsafe { _mm256_movemask_epi8(mem::transmute::<m8x32, __m256i>(m)) as u32 }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Header {
+    n1: usize,
+    n2: usize,
+    c: u8,
+}
+
+#[inline]
+fn parse_number(s: &[u8], i: &mut usize) -> Option<usize> {
+    let start = *i;
+    let mut n = 0usize;
+    while let Some(&d) = s.get(*i) {
+        let d = d.wrapping_sub(b'0');
+        if d > 9 {
+            break;
+        }
+        n = n.wrapping_mul(10).wrapping_add(d as usize); // too many digits is thrown out below
+        *i += 1;
+    }
+    Some(n).filter(|_| *i > start && *i - start <= 9)
+}
+
+#[inline]
+fn parse_header(s: &[u8], i: &mut usize) -> Option<Header> {
+    // "n1-n2 c: ", leaving `i` at the start of the password; none of it can be a newline, so
+    // a header never runs into the next line
+    let n1 = parse_number(s, i)?;
+    if s.get(*i) != Some(&b'-') {
+        return None;
+    }
+    *i += 1;
+    let n2 = parse_number(s, i)?;
+    match s.get(*i..*i + 4)? {
+        &[b' ', c, b':', b' '] if c != b'\n' => {
+            *i += 4;
+            Some(Header { n1, n2, c })
+        }
+        _ => None,
+    }
+}
+
+#[inline]
+fn check(header: &Header, count: usize, at: impl Fn(usize) -> bool) -> (u32, u32) {
+    let valid1 = count >= header.n1 && count <= header.n2;
+    let valid2 = at(header.n1) != at(header.n2);
+    (valid1 as u32, valid2 as u32)
+}
+
+#[inline]
+fn low(n: usize) -> u32 {
+    // the bits below `n`, for `n <= 32`
+    (((1u64) << n) - 1) as u32
+}
+
+#[inline]
+fn parse_digits(s: &[u8]) -> usize {
+    s.iter().fold(0, |n, &d| n * 10 + (d - b'0') as usize)
+}
+
+#[inline]
+fn check_vector(s: &[u8], i: usize) -> Option<(usize, (u32, u32))> {
+    // a whole line inside the vector loaded at its start, as "n1-n2 c: pass\n"; returns its
+    // length along with its validity, or `None` if it doesn't fit or doesn't look like that
+    let v: u8x32 = read_lane(s, i);
+    let mask = |c: u8| bitmask(v.eq(u8x32::splat(c)));
+    let (ends, colons, dashes, spaces) = (mask(b'\n'), mask(b':'), mask(b'-'), mask(b' '));
+    let digits = bitmask((v - u8x32::splat(b'0')).lt(u8x32::splat(10)));
+    let end = ends.trailing_zeros() as usize;
+    let sep = colons.trailing_zeros() as usize;
+    let dash = dashes.trailing_zeros() as usize;
+    let well_formed = ends != 0
+        && sep + 2 <= end
+        && (1..=9).contains(&dash)
+        && dash + 3 < sep
+        && sep - 3 - dash <= 9
+        && digits & low(sep - 2) == low(sep - 2) & !(1 << dash)
+        && spaces & (1 << (sep - 2)) != 0
+        && spaces & (1 << (sep + 1)) != 0;
+    if !well_formed {
+        return None;
+    }
+    let line = &s[i..i + end];
+    let header = Header {
+        n1: parse_digits(&line[..dash]),
+        n2: parse_digits(&line[dash + 1..sep - 2]),
+        c: line[sep - 1],
+    };
+    let start = sep + 2;
+    let hits = (mask(header.c) & low(end)) >> start;
+    let len = end - start;
+    let valid = check(&header, hits.count_ones() as usize, |n| {
+        n >= 1 && n <= len && hits & (1 << (n - 1)) != 0
+    });
+    Some((end, valid))
+}
+
+#[inline]
+pub fn count_valid(s: &[u8]) -> (u32, u32) {
+    assert!(std::is_x86_feature_detected!("avx2"));
+    let (mut valid1, mut valid2) = (0, 0);
+    let mut i = 0;
+    while i < s.len() {
+        if s.len() - i >= LANES {
+            if let Some((len, (n1, n2))) = check_vector(s, i) {
+                i += len + 1;
+                valid1 += n1;
+                valid2 += n2;
+                continue;
+            }
+        }
+        let header = match parse_header(s, &mut i) {
+            Some(header) => header,
+            None => {
+                // skip the rest of the line
+                i += memchr(b'\n', &s[i..]).map_or(s.len(), |n| n + 1);
+                continue;
+            }
+        };
+        // the last line may not end in a newline
+        let len = memchr(b'\n', &s[i..]).unwrap_or(s.len() - i);
+        let pass = &s[i..i + len];
+        i += len + 1;
+        let count = pass.iter().filter(|&&c| c == header.c).count();
+        let (n1, n2) = check(&header, count, |n| {
+            n.checked_sub(1).and_then(|n| pass.get(n)) == Some(&header.c)
+        });
+        valid1 += n1;
+        valid2 += n2;
+    }
+    (valid1, valid2)
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u32 {
+    count_valid(s).0
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u32 {
+    count_valid(s).1
+}
+
+#[test]
+fn test_day02_simd() {
+    use super::{CountRange, Positional};
+    assert_eq!(count_valid(super::input()), (477, 686));
+    let s = super::input().strip_suffix(b"\n").unwrap().repeat(150);
+    assert_eq!(count_valid(&s), (477 * 150, 686 * 150));
+    assert_eq!(super::part1(&s), 477 * 150); // past what the scalar count used to hold
+
+    // random well-formed lines, some too long for the columns, across several batches
+    let mut seed = 7u32;
+    let mut next = |n: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (seed >> 8) % n
+    };
+    let mut s = String::new();
+    for _ in 0..1000 {
+        let len = if next(10) == 0 {
+            30 + next(5)
+        } else {
+            next(20)
+        };
+        let pass: String = (0..len).map(|_| (b'a' + next(3) as u8) as char).collect();
+        let (n1, n2) = (next(300), next(300));
+        s += &format!(
+            "{}-{} {}: {}\n",
+            n1,
+            n2,
+            (b'a' + next(3) as u8) as char,
+            pass
+        );
+    }
+    let s = s.as_bytes();
+    let scalar = (
+        super::count_valid(s, &CountRange),
+        super::count_valid(s, &Positional),
+    );
+    assert_eq!(count_valid(s), scalar);
+    // malformed or truncated lines are skipped rather than read past
+    let s = b"1-3 a: abc\n\n1-3\n1-3 a\n-3 a: a\n1-3 a:\n1-x a: a\n2-3 b: bbb";
+    assert_eq!(count_valid(s), (2, 1));
+}
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 use arrayvec::ArrayVec;
 
+use crate::registry::Strategy;
 use crate::utils::*;
 
 const MAX_WIDTH: usize = 32;
@@ -46,37 +47,36 @@ impl Cycle {
     fn eval(&self, s: &[u8]) -> u8 {
         let n_full_cycles = s.len() / self.len;
 
-        let f = move |k: usize| unsafe { *self.steps.get_unchecked(k) };
-        let g = |p: *const u8, i: u16| unsafe { (*p.add(i as usize) == b'#') as u8 };
+        let f = move |k: usize| self.steps.get_at(k) as usize;
+        let g = |p: &[u8], i: usize| (p.get_at(i) == b'#') as u8;
 
         const BATCH_SIZE: usize = 8;
         let n_batches = self.steps.len() / BATCH_SIZE;
         let rem_start = n_batches * BATCH_SIZE;
 
-        let mut p = s.as_ptr();
+        let mut p = s;
         let mut count = 0;
-        unsafe {
-            for _ in 0..n_full_cycles {
-                let mut k = 0;
-                for _ in 0..n_batches {
-                    let (i0, i1, i2, i3) = (f(k + 0), f(k + 1), f(k + 2), f(k + 3));
-                    let (i4, i5, i6, i7) = (f(k + 4), f(k + 5), f(k + 6), f(k + 7));
-                    let (c0, c1, c2, c3) = (g(p, i0), g(p, i1), g(p, i2), g(p, i3));
-                    let (c4, c5, c6, c7) = (g(p, i4), g(p, i5), g(p, i6), g(p, i7));
-                    count += c0 + c1 + c2 + c3;
-                    count += c4 + c5 + c6 + c7;
-                    k += BATCH_SIZE;
-                }
-                for j in rem_start..self.steps.len() {
-                    count += g(p, f(j));
-                }
-                p = p.add(self.len);
+        for _ in 0..n_full_cycles {
+            let mut k = 0;
+            for _ in 0..n_batches {
+                let (i0, i1, i2, i3) = (f(k + 0), f(k + 1), f(k + 2), f(k + 3));
+                let (i4, i5, i6, i7) = (f(k + 4), f(k + 5), f(k + 6), f(k + 7));
+                let (c0, c1, c2, c3) = (g(p, i0), g(p, i1), g(p, i2), g(p, i3));
+                let (c4, c5, c6, c7) = (g(p, i4), g(p, i5), g(p, i6), g(p, i7));
+                count += c0 + c1 + c2 + c3;
+                count += c4 + c5 + c6 + c7;
+                k += BATCH_SIZE;
             }
-            let (p_end, mut j) = (s.as_ptr().add(s.len()), 0);
-            while p < p_end && j < self.steps.len() {
+            for j in rem_start..self.steps.len() {
                 count += g(p, f(j));
-                j += 1;
             }
+            p = p.advance(self.len);
+        }
+        // the offsets only grow, so the partial cycle stops at the first one past the end
+        let mut j = 0;
+        while j < self.steps.len() && f(j) < p.len() {
+            count += g(p, f(j));
+            j += 1;
         }
         count
     }
@@ -116,6 +116,53 @@ pub fn part2(s: &[u8]) -> u32 {
         * (Cycle::get(w, 1, 2).eval(s) as u32)
 }
 
+// the map is walked in place, so the raw input is the parsed form
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u8 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u32 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u8, u32) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub static STRATEGIES: &[Strategy] = &[
+    Strategy {
+        name: "cycle",
+        part: 1,
+        solver: |s| part1(s).to_string(),
+    },
+    Strategy {
+        name: "slow",
+        part: 1,
+        solver: |s| slow::part1(s).to_string(),
+    },
+    Strategy {
+        name: "cycle",
+        part: 2,
+        solver: |s| part2(s).to_string(),
+    },
+    Strategy {
+        name: "slow",
+        part: 2,
+        solver: |s| slow::part2(s).to_string(),
+    },
+];
+
 pub mod slow {
     use crate::utils::*;
 
@@ -136,7 +183,7 @@ pub mod slow {
         #[inline(always)]
         pub fn process(&mut self, y: u8, line: &[u8], w: u8) {
             if y % self.dy == 0 {
-                self.n += (unsafe { *line.get_unchecked(self.x as usize) } == b'#') as u8;
+                self.n += (line.get_at(self.x as usize) == b'#') as u8;
                 self.x = (self.x + self.dx) % w;
             }
         }
@@ -188,3 +235,9 @@ fn test_day03_part1() {
 fn test_day03_part2() {
     assert_eq!(part2(input()), 3772314000);
 }
+
+#[test]
+fn test_day03_slow() {
+    assert_eq!(slow::part1(input()), 195);
+    assert_eq!(slow::part2(input()), 3772314000);
+}
@@ -1,3 +1,5 @@
+use std::io::{self, BufRead};
+
 use memchr::Memchr2;
 
 use crate::utils::*;
@@ -78,6 +80,44 @@ pub fn part2(s: &[u8]) -> u16 {
     valid_passports
 }
 
+// passports are validated while scanning, so there is nothing to keep
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u16 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, u16) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    let mut sum = 0;
+    for_each_group(r, |group| sum += part1(group) as u64)?;
+    Ok(sum)
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    let mut sum = 0;
+    for_each_group(r, |group| sum += part2(group) as u64)?;
+    Ok(sum)
+}
+
 #[test]
 fn test_day04_part1() {
     assert_eq!(part1(input()), 230);
@@ -87,3 +127,16 @@ fn test_day04_part1() {
 fn test_day04_part2() {
     assert_eq!(part2(input()), 156);
 }
+
+#[test]
+fn test_day04_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        230
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        156
+    );
+}
@@ -2,8 +2,8 @@ use crate::utils::*;
 
 #[inline(always)]
 fn mangle(s: &[u8]) -> u64 {
-    let mut hi = unsafe { u64::from_be_bytes(*s.as_ptr().cast()) };
-    let last = &mut hi as *mut _ as *mut u8; // le/x86
+    let hi = s.get_u64_be();
+    let last = hi as u8; // the 8th byte
     let lo1 = s.get_at(8);
     let lo2 = s.get_at(9);
     /*
@@ -26,14 +26,14 @@ fn mangle(s: &[u8]) -> u64 {
         'RRR': 247,
     }
      */
-    unsafe { *last = ((*last << 1) | lo1 | (lo2 >> 4)) ^ 0xff };
-    hi
+    let last = ((last << 1) | lo1 | (lo2 >> 4)) ^ 0xff;
+    (hi & !0xff) | (last as u64)
 }
 
 #[inline]
 fn unmangle(m: u64) -> u16 {
     let mut a: [u8; 10] = Default::default();
-    unsafe { *a.as_mut_ptr().cast() = m.to_be_bytes() };
+    a[..8].copy_from_slice(&m.to_be_bytes());
     let lo = match a[7] ^ 0xff {
         220 => b"LLL",
         221 => b"LLR",
@@ -150,6 +150,30 @@ pub fn part2(s: &[u8]) -> u16 {
     unmangle(xa ^ xb ^ x)
 }
 
+// both parts decode the boarding passes on the fly from the raw bytes
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u16 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, u16) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
 #[test]
 fn test_day05_part1() {
     assert_eq!(part1(input()), 813);
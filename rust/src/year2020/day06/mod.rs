@@ -0,0 +1,110 @@
+use std::io::{self, BufRead};
+
+use crate::utils::*;
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u16 {
+    // answers are tracked by byte value, so any character counts as a question
+    let mut prev = 0;
+    let mut group = BitSet::new();
+    let mut sum = 0u16;
+    for &c in s {
+        if c != b'\n' {
+            group.insert(c as usize);
+        } else if prev == b'\n' {
+            sum = sum.wrapping_add(group.len() as u16);
+            group.clear();
+        }
+        prev = c;
+    }
+    sum
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u16 {
+    let all = BitSet::full(256);
+    let mut prev = 0;
+    let mut person = BitSet::new();
+    let mut group = all.clone();
+    let mut sum = 0u16;
+    for &c in s {
+        if c != b'\n' {
+            person.insert(c as usize);
+        } else if prev != b'\n' {
+            group &= &person;
+            person.clear();
+        } else {
+            sum = sum.wrapping_add(group.len() as _);
+            person.clear();
+            group = all.clone();
+        }
+        prev = c;
+    }
+    sum
+}
+
+// group answers are folded into bitsets as they are read
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u16 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, u16) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    let mut sum = 0;
+    for_each_group(r, |group| sum += part1(group) as u64)?;
+    Ok(sum)
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    let mut sum = 0;
+    for_each_group(r, |group| sum += part2(group) as u64)?;
+    Ok(sum)
+}
+
+#[test]
+fn test_day06_part1() {
+    assert_eq!(part1(input()), 7120);
+}
+
+#[test]
+fn test_day06_part2() {
+    assert_eq!(part2(input()), 3570);
+}
+
+#[test]
+fn test_day06_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        7120
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        3570
+    );
+}
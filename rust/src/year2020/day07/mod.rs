@@ -1,5 +1,38 @@
+use crate::params::{self, ParamError};
 use crate::utils::*;
 
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub target: String, // the bag colour, adjective and colour name
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            target: "shiny gold".into(),
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        match key {
+            "target" => match value.split_once(' ') {
+                Some((adj, col)) if !adj.is_empty() && !col.is_empty() && !col.contains(' ') => {
+                    self.target = value.into()
+                }
+                _ => return Err(ParamError::InvalidValue(key.into(), value.into())),
+            },
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![("target", self.target.clone())]
+    }
+}
+
 const N_COLUMNS: usize = 32;
 const N_ROWS: usize = 1024;
 
@@ -22,14 +55,12 @@ impl Lookup2 {
 
     #[inline]
     pub fn get(&mut self, key: u16) -> u8 {
-        unsafe {
-            let value = self.map.get_unchecked_mut(key as usize);
-            if *value == 0xff {
-                *value = self.count;
-                self.count += 1;
-            }
-            *value
+        let value = self.map.get_mut_at(key as usize);
+        if *value == 0xff {
+            *value = self.count;
+            self.count += 1;
         }
+        *value
     }
 }
 
@@ -55,14 +86,12 @@ impl Lookup6 {
         let i1 = self.tables[1].get(b1) as u16;
         let i2 = self.tables[2].get(b2) as u16;
         let i = (i0 << 10) | (i1 << 5) | i2;
-        unsafe {
-            let v = self.master.get_unchecked_mut(i as usize);
-            if *v == Id::MAX {
-                *v = self.count;
-                self.count += 1;
-            }
-            *v
+        let v = self.master.get_mut_at(i as usize);
+        if *v == Id::MAX {
+            *v = self.count;
+            self.count += 1;
         }
+        *v
     }
 }
 
@@ -94,6 +123,13 @@ impl Graph {
         self.table.get(b0, b1, b2) as _
     }
 
+    #[inline]
+    pub fn encode_name(&mut self, name: &str) -> Id {
+        // names are encoded from their first bytes, so pad them the way they appear in the input
+        let name = [name.as_bytes(), b" bags"].concat();
+        self.encode_id(&name, name.skip_past(b' ', 0))
+    }
+
     #[inline]
     fn parse_id<'a>(&mut self, s: &'a [u8], i: usize) -> (&'a [u8], Id) {
         let adj = s;
@@ -110,13 +146,12 @@ impl Graph {
             return s.skip_past(b'.', 1);
         }
         let mut p = s;
-        let row = unsafe { self.get_row(src) };
         loop {
             let n = p.get_digit() as u16;
             let (s, dst) = self.parse_id(&p[2..], 3 + ((n != 1) as usize));
             match self.mode {
-                Mode::CountParents => unsafe { self.add_node_parents(src, dst) },
-                Mode::CountChildren => unsafe { self.add_node_children(row, dst, n) },
+                Mode::CountParents => self.add_node_parents(src, dst),
+                Mode::CountChildren => self.add_node_children(src, dst, n),
             }
             p = &s[2..];
             if s.get_first() == b'.' {
@@ -133,25 +168,20 @@ impl Graph {
     }
 
     #[inline]
-    unsafe fn get_row(&mut self, row: Id) -> *mut Id {
-        (*self.graph.as_mut_ptr())
-            .as_mut_ptr()
-            .add((row as usize) * N_COLUMNS)
-    }
-
-    #[inline]
-    unsafe fn add_node_parents(&mut self, src: Id, dst: Id) {
-        let row = self.get_row(dst);
-        *row += 1;
-        *row.add(*row as usize) = src;
+    fn add_node_parents(&mut self, src: Id, dst: Id) {
+        let row = self.graph.get_mut_at(dst as usize);
+        let len = row.get_at(0) + 1;
+        row.set_at(0, len);
+        row.set_at(len as usize, src);
     }
 
     #[inline]
-    unsafe fn add_node_children(&mut self, row: *mut Id, dst: Id, n: Id) {
-        let offset = 1 + 2 * (*row as usize);
-        *row += 1;
-        *row.add(offset) = dst;
-        *row.add(offset + 1) = n;
+    fn add_node_children(&mut self, src: Id, dst: Id, n: Id) {
+        let row = self.graph.get_mut_at(src as usize);
+        let offset = 1 + 2 * (row.get_at(0) as usize);
+        row.add_at(0, 1);
+        row.set_at(offset, dst);
+        row.set_at(offset + 1, n);
     }
 
     #[inline]
@@ -205,6 +235,14 @@ pub fn part1(s: &[u8]) -> u32 {
     g.count(target)
 }
 
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> u32 {
+    let mut g = Graph::new(Mode::CountParents);
+    g.parse_input(s);
+    let target = g.encode_name(&params.target);
+    g.count(target)
+}
+
 #[inline]
 pub fn part2(s: &[u8]) -> u32 {
     let mut g = Graph::new(Mode::CountChildren);
@@ -213,6 +251,38 @@ pub fn part2(s: &[u8]) -> u32 {
     g.count(target)
 }
 
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> u32 {
+    let mut g = Graph::new(Mode::CountChildren);
+    g.parse_input(s);
+    let target = g.encode_name(&params.target);
+    g.count(target)
+}
+
+// each part builds its own graph (forward or reverse edges) from the raw input
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u32 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u32 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u32, u32) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
 #[test]
 fn test_day07_part1() {
     assert_eq!(part1(input()), 179);
@@ -222,3 +292,18 @@ fn test_day07_part1() {
 fn test_day07_part2() {
     assert_eq!(part2(input()), 18925);
 }
+
+#[test]
+fn test_day07_params() {
+    assert_eq!(part1_with(input(), &Params::default()), 179);
+    let params = Params {
+        target: "dull yellow".into(),
+    };
+    assert_eq!(part1_with(input(), &params), 339);
+    assert_eq!(part2_with(input(), &params), 32);
+    let params = Params {
+        target: "dark red".into(), // shorter than the 4 bytes used to identify a colour
+    };
+    assert_eq!(part1_with(input(), &params), 2);
+    assert_eq!(part2_with(input(), &params), 466569670);
+}
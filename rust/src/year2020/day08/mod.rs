@@ -1,6 +1,9 @@
+use std::fmt;
+
+use crate::explain::{Explain, Trace};
 use crate::utils::*;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Op {
     Nop = 0,
@@ -14,6 +17,16 @@ impl Default for Op {
     }
 }
 
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Nop => "nop",
+            Self::Jmp => "jmp",
+            Self::Acc => "acc",
+        })
+    }
+}
+
 impl Op {
     #[inline]
     pub fn invert(self) -> Self {
@@ -27,7 +40,7 @@ impl Op {
 
 type Pos = i16;
 
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
 pub struct Cmd {
     op: Op,
     arg: i16,
@@ -55,6 +68,12 @@ impl Cmd {
     }
 }
 
+impl fmt::Display for Cmd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:+}", self.op, self.arg)
+    }
+}
+
 impl Cmd {
     #[inline]
     pub fn try_parse(s: &mut &[u8]) -> Option<Self> {
@@ -88,6 +107,14 @@ pub struct Runner {
     n_cmds: usize,
 }
 
+impl fmt::Display for Runner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cmds()
+            .iter()
+            .try_for_each(|cmd| writeln!(f, "{}", cmd))
+    }
+}
+
 impl Runner {
     pub fn from_input(mut s: &[u8]) -> Self {
         let mut cmds = [Cmd::default(); MAX_CMDS];
@@ -99,23 +126,42 @@ impl Runner {
         Self { cmds, n_cmds }
     }
 
+    #[inline]
+    pub fn cmds(&self) -> &[Cmd] {
+        &self.cmds[..self.n_cmds]
+    }
+
+    #[inline]
     pub fn execute(&self) -> i16 {
+        self.execute_explained(&mut ())
+    }
+
+    pub fn execute_explained(&self, e: &mut impl Explain) -> i16 {
         let (mut pos, mut acc) = (0, 0);
         let mut visited = [false; MAX_CMDS];
         loop {
-            let v = unsafe { visited.get_unchecked_mut(pos as usize) };
+            let v = visited.get_mut_at(pos as usize);
             if *v {
+                e.step(format_args!(
+                    "loop: instruction {} runs twice, acc = {}",
+                    pos, acc
+                ));
                 break acc;
             }
-            unsafe { *self.cmds.get_unchecked(pos as usize) }.execute(&mut pos, &mut acc);
+            self.cmds.get_at(pos as usize).execute(&mut pos, &mut acc);
             *v = true;
         }
     }
 
     #[inline]
     pub fn find_bug_and_execute(&self) -> i16 {
+        self.find_bug_and_execute_explained(&mut ())
+    }
+
+    #[inline]
+    pub fn find_bug_and_execute_explained(&self, e: &mut impl Explain) -> i16 {
         let mut seen = [false; MAX_CMDS];
-        self.traverse_and_flip(0, 0, false, &mut seen)
+        self.traverse_and_flip(0, 0, false, &mut seen, e)
     }
 
     #[inline]
@@ -125,19 +171,24 @@ impl Runner {
         acc: i16,
         flipped: bool,
         seen: &mut [bool; MAX_CMDS],
+        e: &mut impl Explain,
     ) -> i16 {
         let i = pos as usize;
-        if pos < 0 || unsafe { *seen.get_unchecked(i) } {
+        if pos < 0 || seen.get_at(i) {
             return NULL;
         } else if pos >= (self.n_cmds as Pos) {
+            e.step(format_args!(
+                "exit: instruction {} reached, acc = {}",
+                pos, acc
+            ));
             return acc;
         }
-        unsafe { *seen.get_unchecked_mut(i as usize) = true };
-        let cmd = unsafe { *self.cmds.get_unchecked(pos as usize) };
+        seen.set_at(i, true);
+        let cmd = self.cmds.get_at(i);
         {
             let (mut pos, mut acc) = (pos, acc);
             cmd.execute(&mut pos, &mut acc);
-            let acc = self.traverse_and_flip(pos, acc, flipped, seen);
+            let acc = self.traverse_and_flip(pos, acc, flipped, seen, e);
             if acc != NULL {
                 return acc;
             }
@@ -145,8 +196,16 @@ impl Runner {
         if !flipped {
             let (mut pos, mut acc) = (pos, acc);
             cmd.invert().execute(&mut pos, &mut acc);
-            let acc = self.traverse_and_flip(pos, acc, true, seen);
+            let acc = self.traverse_and_flip(pos, acc, true, seen, e);
             if acc != NULL {
+                // steps are recorded while unwinding, so the flip comes after the exit
+                e.step(format_args!(
+                    "flip: instruction {} from {:?} {:+} to {:?}",
+                    i,
+                    cmd.op,
+                    cmd.arg,
+                    cmd.invert().op
+                ));
                 return acc;
             }
         }
@@ -159,14 +218,50 @@ pub fn input() -> &'static [u8] {
     include_bytes!("input.txt")
 }
 
+pub type Parsed = Runner;
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed {
+    Runner::from_input(s)
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.to_string()
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> i16 {
+    s.execute()
+}
+
 #[inline]
 pub fn part1(s: &[u8]) -> i16 {
-    Runner::from_input(s).execute()
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> i16 {
+    s.find_bug_and_execute()
 }
 
 #[inline]
 pub fn part2(s: &[u8]) -> i16 {
-    Runner::from_input(s).find_bug_and_execute()
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (i16, i16) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub fn explain(s: &[u8]) -> String {
+    let runner = parse(s);
+    let mut trace = Trace::default();
+    runner.execute_explained(&mut trace);
+    runner.find_bug_and_execute_explained(&mut trace);
+    trace.into()
 }
 
 #[test]
@@ -178,3 +273,15 @@ fn test_day08_part1() {
 fn test_day08_part2() {
     assert_eq!(part2(input()), 1000);
 }
+
+#[test]
+fn test_day08_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day08_explain() {
+    let trace = explain(input());
+    assert!(trace.starts_with("loop: instruction 269 runs twice, acc = 2058\n"));
+    assert!(trace.contains("flip: instruction 286 from Jmp +165 to Nop\n"));
+}
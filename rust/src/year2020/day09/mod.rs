@@ -0,0 +1,242 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead};
+
+use arrayvec::ArrayVec;
+
+use crate::params::{self, parse_value, ParamError};
+use crate::utils::*;
+
+type T = i64;
+const N: usize = 25;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub preamble: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { preamble: N }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        match key {
+            "preamble" => match parse_value(key, value)? {
+                preamble if preamble >= 2 => self.preamble = preamble, // room for a pair
+                _ => {
+                    return Err(ParamError::OutOfRange(
+                        key.into(),
+                        value.into(),
+                        "at least 2".into(),
+                    ))
+                }
+            },
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![("preamble", self.preamble.to_string())]
+    }
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+pub fn part1(mut s: &[u8]) -> T {
+    let mut a = ArrayVec::<[T; 1024]>::new();
+    for _ in 0..N {
+        let x = parse_int_fast(&mut s, 1, 14);
+        a.push_fast(x);
+    }
+    let mut p = 0;
+    'next: loop {
+        let x: T = parse_int_fast(&mut s, 1, 14);
+        for i in 0..(N - 1) {
+            let v = x.wrapping_sub(a.get_at(p + i));
+            for j in i + 1..N {
+                if a.get_at(p + j) == v {
+                    a.push_fast(x);
+                    p += 1;
+                    continue 'next;
+                }
+            }
+        }
+        break x;
+    }
+}
+
+pub type Parsed = Vec<T>;
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed {
+    let mut nums = Vec::with_capacity(s.len() / 8);
+    while s.len() > 1 {
+        nums.push(parse_int_fast(&mut s, 1, 14));
+    }
+    nums
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    to_lines(s)
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> T {
+    solve1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve1_with(s: &Parsed, params: &Params) -> T {
+    let n = params.preamble;
+    s.windows(n + 1)
+        .find(|w| !is_pair_sum(&w[..n], w[n]))
+        .map_or(0, |w| w[n])
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> T {
+    solve1_with(&parse(s), params)
+}
+
+#[inline]
+fn find_range(nums: &[T], target: T) -> T {
+    let (mut start, mut sum) = (0, 0);
+    for (end, &x) in nums.iter().enumerate() {
+        sum += x;
+        while sum > target {
+            sum -= nums[start];
+            start += 1;
+        }
+        if sum == target && end > start {
+            let (min, max) = nums[start..=end]
+                .iter()
+                .fold((T::MAX, T::MIN), |(min, max), &x| (min.min(x), max.max(x)));
+            return min + max;
+        }
+    }
+    0
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> T {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve2_with(s: &Parsed, params: &Params) -> T {
+    find_range(s, solve1_with(s, params))
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> T {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> T {
+    solve2_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (T, T) {
+    let s = parse(s);
+    let target = solve1(&s);
+    (target, find_range(&s, target))
+}
+
+#[inline]
+fn is_pair_sum(window: &[T], x: T) -> bool {
+    (0..(window.len() - 1)).any(|i| window[i + 1..].contains(&x.wrapping_sub(window[i])))
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<T> {
+    // only the last N numbers are kept around, in a ring buffer
+    let mut window = [0; N];
+    let (mut n, mut answer) = (0, None);
+    for_each_line(r, |mut line| {
+        if answer.is_none() {
+            let x = parse_int_fast(&mut line, 1, 14);
+            if n >= N && !is_pair_sum(&window, x) {
+                answer = Some(x);
+            }
+            window[n % N] = x;
+            n += 1;
+        }
+    })?;
+    Ok(answer.unwrap_or(0))
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R, target: T) -> io::Result<T> {
+    // the input can't be rewound, so the target (part 1 answer) has to be known upfront;
+    // only the numbers in the current contiguous range are kept around
+    let mut range = VecDeque::new();
+    let (mut sum, mut answer) = (0, None);
+    for_each_line(r, |mut line| {
+        if answer.is_none() {
+            let x: T = parse_int_fast(&mut line, 1, 14);
+            range.push_back(x);
+            sum += x;
+            while sum > target {
+                sum -= range.pop_front().unwrap_or(0);
+            }
+            if sum == target && range.len() > 1 {
+                let (min, max) = range
+                    .iter()
+                    .fold((T::MAX, T::MIN), |(min, max), &x| (min.min(x), max.max(x)));
+                answer = Some(min + max);
+            }
+        }
+    })?;
+    Ok(answer.unwrap_or(0))
+}
+
+#[test]
+fn test_day09_part1() {
+    assert_eq!(part1(input()), 50047984);
+}
+
+#[test]
+fn test_day09_part2() {
+    assert_eq!(part2(input()), 5407707);
+}
+
+#[test]
+fn test_day09_solve_both() {
+    assert_eq!(solve_both(input()), (50047984, 5407707));
+}
+
+#[test]
+fn test_day09_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day09_reader() {
+    use std::io::BufReader;
+    let part1 = part1_reader(BufReader::with_capacity(7, input())).unwrap();
+    assert_eq!(part1, 50047984);
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input()), part1).unwrap(),
+        5407707
+    );
+}
+
+#[test]
+fn test_day09_params() {
+    let s = b"35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n\
+              102\n117\n150\n182\n127\n219\n299\n277\n309\n576\n";
+    let params = Params { preamble: 5 };
+    assert_eq!(part1_with(s, &params), 127);
+    assert_eq!(part2_with(s, &params), 62);
+    assert_eq!(part1_with(input(), &Params::default()), 50047984);
+}
@@ -0,0 +1,160 @@
+use std::io::{self, BufRead};
+
+use arrayvec::ArrayVec;
+
+use crate::utils::*;
+
+// the adapters, sorted by joltage
+pub type Parsed = ArrayVec<[u8; 256]>;
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed {
+    let mut a = ArrayVec::new();
+    while s.len() > 1 {
+        a.push_fast(parse_int_fast(&mut s, 1, 3));
+    }
+    quickersort::sort(&mut a);
+    a
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    to_lines(s)
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    let (_, n1, n3) = s.iter().fold((0, 0u8, 0u8), |(prev, n1, n3), &x| {
+        let dx = x - prev;
+        let n1 = n1.wrapping_add((dx == 1) as u8);
+        let n3 = n3.wrapping_add((dx == 3) as u8);
+        (x, n1, n3)
+    });
+    (n1 as u16) * ((n3 + 1) as u16)
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u16 {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn solve2(a: &Parsed) -> usize {
+    let mut m = [0usize; 256];
+    for i in 0..4 {
+        m[i] = 1;
+    }
+    for &x in a {
+        let n = m.get_at(x as _);
+        for i in 1..=3 {
+            m.add_at((x as usize) + i, n);
+        }
+    }
+    m.get_at((a.get_last() + 3) as _)
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> usize {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, usize) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+fn read_present<R: BufRead>(r: R) -> io::Result<[bool; 256]> {
+    // joltages are distinct and small, so a presence table replaces sorting
+    let mut present = [false; 256];
+    let (mut line_no, mut invalid) = (0, None);
+    for_each_line(r, |mut line| {
+        line_no += 1;
+        match parse_int_fast::<u16>(&mut line, 1, 3) {
+            x if x < 256 => present[x as usize] = true,
+            _ => invalid = invalid.or(Some(line_no)),
+        }
+    })?;
+    match invalid {
+        Some(line_no) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("adapter {} is rated above 255 jolts", line_no),
+        )),
+        None => Ok(present),
+    }
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<u16> {
+    let present = read_present(r)?;
+    let (_, n1, n3) =
+        (1..256)
+            .filter(|&x| present[x])
+            .fold((0, 0u16, 0u16), |(prev, n1, n3), x| {
+                // at most 255 adapters, so neither count nor the product can overflow
+                let dx = x - prev;
+                (x, n1 + (dx == 1) as u16, n3 + (dx == 3) as u16)
+            });
+    Ok(n1 * (n3 + 1))
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<usize> {
+    let present = read_present(r)?;
+    let mut m = [0usize; 256];
+    m[0] = 1;
+    let mut last = 0;
+    for x in 1..256 {
+        if present[x] {
+            m[x] = m[x.saturating_sub(3)..x].iter().sum();
+            last = x;
+        }
+    }
+    Ok(m[last])
+}
+
+#[test]
+fn test_day10_part1() {
+    assert_eq!(part1(input()), 2470);
+}
+
+#[test]
+fn test_day10_part2() {
+    assert_eq!(part2(input()), 1973822685184);
+}
+
+#[test]
+fn test_day10_unparse() {
+    // the adapters come back sorted, which changes neither answer
+    let s = b"16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n";
+    let sorted = assert_round_trip(s, |s| unparse(&parse(s)));
+    assert_eq!(sorted, "1\n4\n5\n6\n7\n10\n11\n12\n15\n16\n19\n");
+    assert_eq!(part1(sorted.as_bytes()), part1(s));
+    assert_eq!(part2(sorted.as_bytes()), part2(s));
+    let s = assert_round_trip(input(), |s| unparse(&parse(s)));
+    assert_eq!(parse(s.as_bytes()), parse(input()));
+}
+
+#[test]
+fn test_day10_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        2470
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        1973822685184
+    );
+    // every joltage from 1 to 255 is there, one apart; and 256 doesn't fit in the table
+    let s: String = (1..256).map(|x| format!("{}\n", 256 - x)).collect();
+    assert_eq!(part1_reader(s.as_bytes()).unwrap(), 255);
+    let err = part2_reader(&b"1\n2\n256\n"[..]).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,474 @@
+use std::fmt;
+
+use arrayvec::ArrayVec;
+use packed_simd_2::u8x32;
+
+use crate::params::{self, parse_value, ParamError};
+use crate::registry::Strategy;
+use crate::utils::*;
+
+const MAX_GAPS: usize = 1 << 11;
+
+// how many occupied seats it takes for someone to leave theirs
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub threshold1: u8,
+    pub threshold2: u8,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            threshold1: 4,
+            threshold2: 5,
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        let threshold = match key {
+            "threshold1" => &mut self.threshold1,
+            "threshold2" => &mut self.threshold2,
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        };
+        match parse_value(key, value)? {
+            // there are 8 seats to see, and with fewer than 4 the seats can flip back and forth
+            // forever instead of settling
+            n @ 4..=8 => *threshold = n,
+            _ => {
+                return Err(ParamError::OutOfRange(
+                    key.into(),
+                    value.into(),
+                    "between 4 and 8".into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("threshold1", self.threshold1.to_string()),
+            ("threshold2", self.threshold2.to_string()),
+        ]
+    }
+}
+
+#[inline]
+fn iter_main_diagonals(
+    w: usize,
+    h: usize,
+) -> impl Iterator<Item = impl Iterator<Item = (usize, usize)>> {
+    // iterate over all main diagonals of a rectangle and return coordinates
+    (0..w + h - 1).map(move |s| {
+        let z1 = s.saturating_sub(w - 1);
+        let z2 = s.saturating_sub(h - 1);
+        (z1..=s - z2).map(move |j| (s - j, j))
+    })
+}
+
+#[inline]
+fn iter_off_diagonals(
+    w: usize,
+    h: usize,
+) -> impl Iterator<Item = impl Iterator<Item = (usize, usize)>> {
+    // iterate over all off diagonals of a rectangle and return coordinates
+    (0..w + h - 1).map(move |s| {
+        let z1 = s.saturating_sub(w - 1);
+        let z2 = s.saturating_sub(h - 1);
+        (z1..=s - z2).map(move |j| (w - 1 - (s - j), j))
+    })
+}
+
+// `PAD` is the reach of the local convolution: 2 for 5x5, 3 for 7x7
+#[derive(Clone)]
+pub struct State<const PAD: usize> {
+    states: Grid<u8>,
+    counts: Grid<u8>,
+    gaps: ArrayVec<[(usize, usize); MAX_GAPS]>,
+}
+
+impl<const PAD: usize> State<PAD> {
+    pub fn parse(s: &[u8]) -> Self {
+        assert!(std::is_x86_feature_detected!("avx2"));
+        // store 16 if it's a floor ('.'); 0 otherwise, and padding is floor too
+        let states = Grid::from_bytes(s, PAD, 32, 16, |c| ((c == b'.') as u8) << 4);
+        let counts = Grid::new(states.width(), states.height(), PAD, 32, 0);
+        Self {
+            states,
+            counts,
+            gaps: ArrayVec::new(),
+        }
+    }
+
+    #[inline]
+    fn width(&self) -> usize {
+        self.states.stride() // row width including pre/post padding
+    }
+
+    #[inline]
+    fn lanes(&self) -> usize {
+        self.states.lanes()
+    }
+
+    #[inline]
+    fn height(&self) -> usize {
+        self.states.height()
+    }
+
+    #[inline]
+    fn read_states(&self, pos: usize) -> u8x32 {
+        self.states.read_lane(pos)
+    }
+
+    #[inline]
+    fn read_counts(&self, pos: usize) -> u8x32 {
+        self.counts.read_lane(pos)
+    }
+
+    #[inline]
+    fn write_states(&mut self, pos: usize, states: u8x32) {
+        self.states.write_lane(pos, states)
+    }
+
+    #[inline]
+    fn write_counts(&mut self, pos: usize, counts: u8x32) {
+        self.counts.write_lane(pos, counts)
+    }
+
+    pub fn update_counts_1(&mut self) {
+        let width = self.width();
+        for i in PAD..self.height() + PAD {
+            let m = i * width;
+            let (u, d) = (m - width, m + width);
+            for lane in 0..self.lanes() {
+                let j = PAD + lane * 32;
+                let (l, r) = (j - 1, j + 1);
+                // zero it out
+                let mut counts = u8x32::splat(0);
+                // all cells row above
+                counts += self.read_states(u + l);
+                counts += self.read_states(u + j);
+                counts += self.read_states(u + r);
+                // left/right mid row
+                counts += self.read_states(m + l);
+                counts += self.read_states(m + r);
+                // all cells row below
+                counts += self.read_states(d + l);
+                counts += self.read_states(d + j);
+                counts += self.read_states(d + r);
+                // ignore floors
+                counts &= u8x32::splat(0x0f);
+                // write it
+                self.write_counts(m + j, counts);
+            }
+        }
+    }
+
+    pub fn step_1(&mut self, threshold: u8) -> bool {
+        self.update_counts_1();
+        self.step(threshold)
+    }
+
+    pub fn step_2(&mut self, threshold: u8) -> bool {
+        self.update_counts_2();
+        self.step(threshold)
+    }
+
+    pub fn step(&mut self, threshold: u8) -> bool {
+        let width = self.width();
+        let mut changed = false;
+        for i in PAD..self.height() + PAD {
+            let m = i * width;
+            for lane in 0..self.lanes() {
+                let j = PAD + lane * 32;
+                let k = m + j;
+                let counts = self.read_counts(k);
+                let old = self.read_states(k);
+                let new = old;
+                let new = counts.eq(u8x32::splat(0)).select(u8x32::splat(1), new);
+                let new = counts
+                    .ge(u8x32::splat(threshold))
+                    .select(u8x32::splat(0), new);
+                let new = old.eq(u8x32::splat(16)).select(u8x32::splat(16), new);
+                changed = changed || new.ne(old).any();
+                self.write_states(k, new);
+            }
+        }
+        changed
+    }
+
+    #[inline]
+    fn read2(&self, i1: usize, i2: usize) -> u8x32 {
+        // if the near one is non-floor, use that; otherwise use the far one
+        let s1 = self.read_states(i1);
+        let s2 = self.read_states(i2);
+        s1.ne(u8x32::splat(16)).select(s1, s2)
+    }
+
+    #[inline]
+    fn read3(&self, i1: usize, i2: usize, i3: usize) -> u8x32 {
+        // same as read2(), but uses the 7x7 convolution (3 tiles far)
+        let s12 = self.read2(i1, i2);
+        let s3 = self.read_states(i3);
+        s12.ne(u8x32::splat(16)).select(s12, s3)
+    }
+
+    pub fn update_counts_local(&mut self) {
+        let width = self.width();
+
+        for i in PAD..self.height() + PAD {
+            let m = i * width;
+            let (u1, d1) = (m - 1 * width, m + 1 * width);
+            let (u2, d2) = (m - 2 * width, m + 2 * width);
+            for lane in 0..self.lanes() {
+                let j = PAD + lane * 32;
+                let (l1, r1) = (j - 1, j + 1);
+                let (l2, r2) = (j - 2, j + 2);
+                let mut counts = u8x32::splat(0); // zero it out
+
+                if PAD >= 3 {
+                    // 7x7
+                    let (u3, d3) = (m - 3 * width, m + 3 * width);
+                    let (l3, r3) = (j - 3, j + 3);
+                    counts += self.read3(u1 + l1, u2 + l2, u3 + l3); // up-left
+                    counts += self.read3(u1 + j, u2 + j, u3 + j); // up
+                    counts += self.read3(u1 + r1, u2 + r2, u3 + r3); // up-right
+                    counts += self.read3(m + l1, m + l2, m + l3); // left
+                    counts += self.read3(m + r1, m + r2, m + r3); // right
+                    counts += self.read3(d1 + l1, d2 + l2, d3 + l3); // down-left
+                    counts += self.read3(d1 + j, d2 + j, d3 + j); // down
+                    counts += self.read3(d1 + r1, d2 + r2, d3 + r3); // down-right
+                } else {
+                    // 5x5
+                    counts += self.read2(u1 + l1, u2 + l2); // up-left
+                    counts += self.read2(u1 + j, u2 + j); // up
+                    counts += self.read2(u1 + r1, u2 + r2); // up-right
+                    counts += self.read2(m + l1, m + l2); // left
+                    counts += self.read2(m + r1, m + r2); // right
+                    counts += self.read2(d1 + l1, d2 + l2); // down-left
+                    counts += self.read2(d1 + j, d2 + j); // down
+                    counts += self.read2(d1 + r1, d2 + r2); // down-right
+                }
+
+                counts &= u8x32::splat(0x0f); // ignore floors
+                self.write_counts(m + j, counts); // write it
+            }
+        }
+    }
+
+    #[inline]
+    fn record_gaps(&mut self, indices: impl Iterator<Item = usize>) {
+        let mut prev = None;
+        for (i, index) in indices.enumerate() {
+            let v = self.states.cells().get_at(index);
+            if v != 16 {
+                if let Some((prev_i, prev_index)) = prev {
+                    if i - prev_i > PAD {
+                        // too far apart to be seen by the local convolution
+                        self.gaps.push_fast((prev_index, index));
+                    }
+                }
+                prev = Some((i, index));
+            }
+        }
+    }
+
+    pub fn find_gaps(&mut self) {
+        // find all pairs of non-floor points that are on the same vertical/horizontal/diagonal
+        // line in any of the four directions and have more than one floor between them
+        // (and store absolute indices/offsets from the start of the states/counts arrays)
+        let width = self.width(); // with padding
+        let (x2, y2) = (PAD + self.lanes() * 32, self.height() + PAD);
+        let (x1, y1) = (PAD, PAD); // rectangle coords
+        for y in y1..y2 {
+            self.record_gaps((x1..x2).map(|x| y * width + x)); // horizontal
+        }
+        for x in x1..x2 {
+            self.record_gaps((y1..y2).map(|y| y * width + x)); // vertical
+        }
+        for d in iter_main_diagonals(x2 - x1, y2 - y1) {
+            self.record_gaps(d.map(|(x, y)| (y1 + y) * width + x1 + x)); // main diag
+        }
+        for d in iter_off_diagonals(x2 - x1, y2 - y1) {
+            self.record_gaps(d.map(|(x, y)| (y1 + y) * width + x1 + x)); // off diag
+        }
+    }
+
+    #[inline]
+    pub fn update_counts_2(&mut self) {
+        self.update_counts_local();
+        let (states, counts) = (self.states.cells(), self.counts.cells_mut());
+        for &(i, j) in &self.gaps {
+            counts.add_at(i, states.get_at(j) & 0x0f);
+            counts.add_at(j, states.get_at(i) & 0x0f);
+        }
+    }
+
+    #[inline]
+    pub fn occupied(&self) -> u32 {
+        // this is a simple way of doing it, but could use vectorized sum instead
+        let width = self.width();
+        let mut count = 0;
+        for i in PAD..self.height() + PAD {
+            let m = i * width;
+            for lane in 0..self.lanes() {
+                let j = PAD + lane * 32;
+                let states = self.read_states(m + j);
+                let occupied = (states & u8x32::splat(0x0f)).ne(u8x32::splat(0));
+                count += occupied.bitmask().count_ones();
+            }
+        }
+        count
+    }
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+impl<const PAD: usize> fmt::Display for State<PAD> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width();
+        for i in PAD..self.height() + PAD {
+            let row = &self.states.cells()[i * width + PAD..][..self.states.width()];
+            let row = row.iter().map(|&x| match x {
+                0 => 'L',
+                16 => '.',
+                _ => '#',
+            });
+            writeln!(f, "{}", row.collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+// the padded seat layout; each part runs its own copy to a fixed point
+pub type Parsed = State<2>;
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed {
+    State::parse(s)
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.to_string()
+}
+
+#[inline]
+fn converge1(mut state: State<2>, params: &Params) -> u32 {
+    while state.step_1(params.threshold1) {}
+    state.occupied()
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u32 {
+    solve1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve1_with(s: &Parsed, params: &Params) -> u32 {
+    converge1(s.clone(), params)
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u32 {
+    part1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> u32 {
+    converge1(parse(s), params)
+}
+
+#[inline]
+fn converge2<const PAD: usize>(mut state: State<PAD>, params: &Params) -> u32 {
+    state.find_gaps();
+    while state.step_2(params.threshold2) {}
+    state.occupied()
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u32 {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve2_with(s: &Parsed, params: &Params) -> u32 {
+    converge2(s.clone(), params)
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u32 {
+    part2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> u32 {
+    converge2(parse(s), params)
+}
+
+#[inline]
+pub fn part2_conv7(s: &[u8]) -> u32 {
+    // fewer gaps to patch up, but more reads per lane
+    converge2(State::<3>::parse(s), &Params::default())
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u32, u32) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub static STRATEGIES: &[Strategy] = &[
+    Strategy {
+        name: "conv5",
+        part: 2,
+        solver: |s| part2(s).to_string(),
+    },
+    Strategy {
+        name: "conv7",
+        part: 2,
+        solver: |s| part2_conv7(s).to_string(),
+    },
+];
+
+#[test]
+fn test_day11_part1() {
+    assert_eq!(part1(input()), 2270);
+}
+
+#[test]
+fn test_day11_part2() {
+    assert_eq!(part2(input()), 2042);
+}
+
+#[test]
+fn test_day11_conv7() {
+    assert_eq!(part2_conv7(input()), 2042);
+}
+
+#[test]
+fn test_day11_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+    let mut state = parse(b"L.L\nLLL\n");
+    state.step_1(4);
+    assert_eq!(unparse(&state), "#.#\n###\n");
+}
+
+#[test]
+fn test_day11_params() {
+    let s = b"L.LL.LL.LL\nLLLLLLL.LL\nL.L.L..L..\nLLLL.LL.LL\nL.LL.LL.LL\n\
+              L.LLLLL.LL\n..L.L.....\nLLLLLLLLLL\nL.LLLLLL.L\nL.LLLLL.LL\n";
+    assert_eq!(part1_with(s, &Params::default()), 37);
+    assert_eq!(part2_with(s, &Params::default()), 26);
+    let params = Params {
+        threshold1: 5,
+        threshold2: 4,
+    };
+    assert_eq!(part1_with(s, &params), 46);
+    assert_eq!(part2_with(s, &params), 23);
+}
@@ -1,3 +1,5 @@
+use std::io::{self, BufRead};
+
 use crate::utils::*;
 
 type Coord = i16;
@@ -85,6 +87,48 @@ pub fn part2(mut s: &[u8]) -> Distance {
     pos.manhattan_distance()
 }
 
+// the two parts interpret the same instructions differently while reading
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> Distance {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> Distance {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (Distance, Distance) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<Distance> {
+    let mut pos = Position::new(1, 0);
+    for_each_line(r, |mut line| {
+        pos.parse_and_update(&mut line, |p| &mut p.loc)
+    })?;
+    Ok(pos.manhattan_distance())
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<Distance> {
+    let mut pos = Position::new(10, 1);
+    for_each_line(r, |mut line| {
+        pos.parse_and_update(&mut line, |p| &mut p.dir)
+    })?;
+    Ok(pos.manhattan_distance())
+}
+
 #[test]
 fn test_day12_part1() {
     assert_eq!(part1(input()), 759);
@@ -94,3 +138,16 @@ fn test_day12_part1() {
 fn test_day12_part2() {
     assert_eq!(part2(input()), 45763);
 }
+
+#[test]
+fn test_day12_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        759
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        45763
+    );
+}
@@ -1,3 +1,4 @@
+use crate::explain::{Explain, Trace};
 use crate::utils::*;
 
 #[inline]
@@ -76,9 +77,13 @@ fn solve_crt_pair((a1, n1): (i64, i64), (a2, n2): (i16, i16)) -> (i64, i64) {
 }
 
 #[inline]
-pub fn part2(mut s: &[u8]) -> i64 {
+fn part2_explained(mut s: &[u8], e: &mut impl Explain) -> i64 {
     s = s.advance(1 + memchr::memchr(b'\n', s).unwrap());
     let mut pair = (0, parse_int_fast::<i16>(&mut s, 1, 3) as i64);
+    e.step(format_args!(
+        "bus {} at offset 0: t = 0 (mod {})",
+        pair.1, pair.1
+    ));
     let mut i = 0i16;
     while s.len() > 1 {
         i += 1;
@@ -88,10 +93,54 @@ pub fn part2(mut s: &[u8]) -> i64 {
         }
         let n = parse_int_fast::<i16>(&mut s, 1, 3);
         pair = solve_crt_pair(pair, (n - i, n));
+        e.step(format_args!(
+            "bus {} at offset {}: t = {} (mod {}), folded into t = {} (mod {})",
+            n,
+            i,
+            (n - i).rem_euclid(n),
+            n,
+            pair.0.rem_euclid(pair.1),
+            pair.1
+        ));
     }
     pair.0
 }
 
+#[inline]
+pub fn part2(s: &[u8]) -> i64 {
+    part2_explained(s, &mut ())
+}
+
+// the two parts read different lines of the input
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> i32 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> i64 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (i32, i64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub fn explain(s: &[u8]) -> String {
+    let mut trace = Trace::default();
+    part2_explained(s, &mut trace);
+    trace.into()
+}
+
 #[test]
 fn test_day13_part1() {
     assert_eq!(part1(input()), 6568);
@@ -101,3 +150,11 @@ fn test_day13_part1() {
 fn test_day13_part2() {
     assert_eq!(part2(input()), 554865447501099);
 }
+
+#[test]
+fn test_day13_explain() {
+    let trace = explain(input());
+    assert!(trace
+        .trim_end()
+        .ends_with("t = 554865447501099 (mod 1615033136751203)"));
+}
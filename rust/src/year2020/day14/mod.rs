@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::hash::BuildHasherDefault;
 use std::iter;
 
@@ -8,18 +9,40 @@ use crate::utils::*;
 
 type Map = HashMap<u64, u64, BuildHasherDefault<FxHasher>>;
 
-#[derive(Debug, Copy, Clone, Default)]
-struct Mask {
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Mask {
     mask: u64,
     value: u64,
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Instruction {
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
     Mask(Mask),
     Write { addr: u64, value: u64 },
 }
 
+impl fmt::Display for Mask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = (0..36)
+            .rev()
+            .map(|p| match (self.mask >> p & 1, self.value >> p & 1) {
+                (1, _) => 'X',
+                (_, 1) => '1',
+                _ => '0',
+            });
+        write!(f, "{}", bits.collect::<String>())
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mask(mask) => write!(f, "mask = {}", mask),
+            Self::Write { addr, value } => write!(f, "mem[{}] = {}", addr, value),
+        }
+    }
+}
+
 #[inline]
 fn parse_instructions<'a>(mut s: &'a [u8]) -> impl Iterator<Item = Instruction> + 'a {
     iter::from_fn(move || {
@@ -53,11 +76,23 @@ pub fn input() -> &'static [u8] {
     include_bytes!("input.txt")
 }
 
+pub type Parsed = Vec<Instruction>;
+
 #[inline]
-pub fn part1(s: &[u8]) -> u64 {
+pub fn parse(s: &[u8]) -> Parsed {
+    parse_instructions(s).collect()
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    to_lines(s)
+}
+
+#[inline]
+fn run1(instructions: impl Iterator<Item = Instruction>) -> u64 {
     let mut map = Map::with_capacity_and_hasher(1024, Default::default());
     let (mut masked, mut bitmask) = (0, 0);
-    for instruction in parse_instructions(s) {
+    for instruction in instructions {
         match instruction {
             Instruction::Mask(mask) => {
                 bitmask = mask.mask;
@@ -72,6 +107,16 @@ pub fn part1(s: &[u8]) -> u64 {
     map.values().sum()
 }
 
+#[inline]
+pub fn solve1(s: &Parsed) -> u64 {
+    run1(s.iter().copied())
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u64 {
+    run1(parse_instructions(s))
+}
+
 #[derive(Copy, Clone, Debug)]
 enum Cell {
     Empty,
@@ -80,7 +125,7 @@ enum Cell {
 }
 
 #[inline]
-pub fn part2(s: &[u8]) -> u64 {
+fn run2(instructions: impl Iterator<Item = Instruction>) -> u64 {
     const MASK_LO: u64 = 0x000000000000FFFF;
     const MASK_HI: u64 = 0xFFFFFFFFFFFF0000;
 
@@ -98,7 +143,7 @@ pub fn part2(s: &[u8]) -> u64 {
     let mut mask_now = Mask::default();
     let mut cells = [Cell::Empty; 65536];
 
-    for instruction in parse_instructions(s) {
+    for instruction in instructions {
         match instruction {
             Instruction::Mask(mask_new) => mask_now = mask_new,
             Instruction::Write { addr, value } => {
@@ -139,6 +184,22 @@ pub fn part2(s: &[u8]) -> u64 {
     sum_multi + sum_single
 }
 
+#[inline]
+pub fn solve2(s: &Parsed) -> u64 {
+    run2(s.iter().copied())
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u64 {
+    run2(parse_instructions(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u64, u64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
 #[test]
 fn test_day14_part1() {
     assert_eq!(part1(input()), 7477696999511);
@@ -148,3 +209,8 @@ fn test_day14_part1() {
 fn test_day14_part2() {
     assert_eq!(part2(input()), 3687727854171);
 }
+
+#[test]
+fn test_day14_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
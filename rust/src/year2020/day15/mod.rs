@@ -0,0 +1,249 @@
+use crate::params::{self, parse_value, ParamError};
+use crate::progress::{chunked, Cancelled, Progress};
+use crate::utils::*;
+
+use arrayvec::ArrayVec;
+use itertools::Itertools;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub turns1: u32,
+    pub turns2: u32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            turns1: 2020,
+            turns2: 30_000_000,
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        let turns = match key {
+            "turns1" => &mut self.turns1,
+            "turns2" => &mut self.turns2,
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        };
+        match parse_value(key, value)? {
+            n if n >= 1 => *turns = n,
+            _ => {
+                return Err(ParamError::OutOfRange(
+                    key.into(),
+                    value.into(),
+                    "at least 1".into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("turns1", self.turns1.to_string()),
+            ("turns2", self.turns2.to_string()),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct Sequence {
+    history: Vec<u32>,
+    last: u32,
+    clock: u32,
+}
+
+impl Sequence {
+    #[inline]
+    pub fn new(init: &[u32], max_num: u32) -> Self {
+        let (&last, init) = init.split_last().unwrap();
+        let max_num = init.iter().fold(max_num.max(last), |a, &b| a.max(b));
+        let mut history = vec![u32::MAX; 1 + (max_num as usize)];
+        for (i, &num) in init.iter().enumerate() {
+            history[num as usize] = (i + 1) as u32;
+        }
+        let clock = (init.len() + 1) as u32;
+        Self {
+            history,
+            last,
+            clock,
+        }
+    }
+
+    #[inline]
+    pub fn step(&mut self) {
+        let prev = self.history.get_mut_at(self.last as usize);
+        self.last = self.clock.saturating_sub(*prev);
+        *prev = self.clock;
+        self.clock += 1;
+    }
+
+    #[inline]
+    pub fn nth(init: &[u32], n: u32) -> u32 {
+        if let Some(&num) = init.get((n as usize).wrapping_sub(1)) {
+            return num; // still one of the starting numbers
+        }
+        let mut seq = Self::new(init, n);
+        for _ in seq.clock..n {
+            seq.step();
+        }
+        seq.last
+    }
+
+    #[inline]
+    pub fn nth_with_progress<P: Progress + ?Sized>(
+        init: &[u32],
+        n: u32,
+        progress: &mut P,
+    ) -> Result<u32, Cancelled> {
+        if let Some(&num) = init.get((n as usize).wrapping_sub(1)) {
+            return Ok(num);
+        }
+        let mut seq = Self::new(init, n);
+        let steps = n.saturating_sub(seq.clock) as usize;
+        chunked(steps, progress, |chunk| {
+            for _ in chunk {
+                seq.step();
+            }
+        })?;
+        Ok(seq.last)
+    }
+}
+
+// the starting numbers
+pub type Parsed = ArrayVec<[u32; 8]>;
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed {
+    let mut vec = ArrayVec::new();
+    while s.len() > 1 {
+        vec.push(parse_int_fast(&mut s, 1, 2));
+    }
+    vec
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    format!("{}\n", s.iter().join(","))
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u32 {
+    solve1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve1_with(s: &Parsed, params: &Params) -> u32 {
+    Sequence::nth(s, params.turns1)
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u32 {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> u32 {
+    solve1_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u32 {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u32 {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve2_with(s: &Parsed, params: &Params) -> u32 {
+    Sequence::nth(s, params.turns2)
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> u32 {
+    solve2_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve2_with_progress<P: Progress + ?Sized>(
+    s: &Parsed,
+    params: &Params,
+    progress: &mut P,
+) -> Result<u32, Cancelled> {
+    Sequence::nth_with_progress(s, params.turns2, progress)
+}
+
+#[inline]
+pub fn part2_with_progress<P: Progress + ?Sized>(
+    s: &[u8],
+    params: &Params,
+    progress: &mut P,
+) -> Result<u32, Cancelled> {
+    solve2_with_progress(&parse(s), params, progress)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u32, u32) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[test]
+fn test_day15_part1() {
+    assert_eq!(part1(input()), 412);
+}
+
+#[test]
+fn test_day15_part2() {
+    assert_eq!(part2(input()), 243);
+    assert_eq!(part2(b"0,3,6\n"), 175594); // the example from the puzzle text
+}
+
+#[test]
+fn test_day15_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day15_progress() {
+    let init = parse(input());
+    assert_eq!(Sequence::nth_with_progress(&init, 2020, &mut ()), Ok(412));
+    let mut reports = 0;
+    let mut stop = |_, _| {
+        reports += 1;
+        false
+    };
+    let params = Params::default();
+    assert_eq!(
+        part2_with_progress(input(), &params, &mut stop),
+        Err(Cancelled)
+    );
+    assert_eq!(reports, 1);
+}
+
+#[test]
+fn test_day15_params() {
+    let params = Params {
+        turns1: 10,
+        turns2: 4,
+    };
+    assert_eq!(part1_with(b"0,3,6\n", &params), 0);
+    assert_eq!(part2_with(b"0,3,6\n", &params), 0);
+    let params = Params {
+        turns1: 2,
+        turns2: 3,
+    };
+    assert_eq!(part1_with(b"0,3,6\n", &params), 3);
+    assert_eq!(part2_with(b"0,3,6\n", &params), 6);
+    assert_eq!(part1_with(b"3,1,2\n", &Params::default()), 1836);
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+use std::iter;
+use std::ops::{Deref, RangeInclusive};
+
+use arrayvec::ArrayVec;
+use bstr::ByteSlice;
+use itertools::Itertools;
+
+use crate::explain::{Explain, Trace};
+use crate::utils::*;
+
+const INLINE_FIELDS: usize = 32;
+
+// tickets with up to `INLINE_FIELDS` values (the puzzle's have 20) are kept inline, so reading
+// the nearby tickets doesn't allocate for each of them; longer ones go on the heap
+#[derive(Debug, Clone)]
+enum Ticket {
+    Inline(ArrayVec<[u16; INLINE_FIELDS]>),
+    Spilled(Vec<u16>),
+}
+
+impl Deref for Ticket {
+    type Target = [u16];
+
+    #[inline]
+    fn deref(&self) -> &[u16] {
+        match self {
+            Self::Inline(values) => values,
+            Self::Spilled(values) => values,
+        }
+    }
+}
+
+type Rule = (String, [RangeInclusive<u16>; 2]);
+
+// Workaround for a bug in lifetimes with impl traits:
+// https://github.com/rust-lang/rust/issues/61756
+pub trait Captures<'a> {}
+impl<'a, T: ?Sized> Captures<'a> for T {}
+
+#[inline]
+fn parse_valid_ranges<'a, 'b>(
+    s: &'a mut &'b [u8],
+) -> impl Iterator<Item = (&'b [u8], [RangeInclusive<u16>; 2])> + 'a + Captures<'b> {
+    iter::from_fn(move || {
+        if s.get_first() != b'\n' {
+            let name = &s[..s.memchr(b':')];
+            *s = s.skip_past(b':', 1);
+            let s0 = parse_int_fast(s, 1, 3);
+            let e0 = parse_int_fast(s, 1, 3);
+            *s = s.advance(3);
+            let s1 = parse_int_fast(s, 1, 3);
+            let e1 = parse_int_fast(s, 1, 3);
+            Some((name, [s0..=e0, s1..=e1]))
+        } else {
+            None
+        }
+    })
+}
+
+#[inline]
+fn parse_ticket(s: &mut &[u8], n: usize) -> Ticket {
+    if n <= INLINE_FIELDS {
+        let mut ticket = ArrayVec::new();
+        for _ in 0..n {
+            ticket.push_fast(parse_int_fast(s, 1, 3));
+        }
+        Ticket::Inline(ticket)
+    } else {
+        Ticket::Spilled((0..n).map(|_| parse_int_fast(s, 1, 3)).collect())
+    }
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+// the ticket rules as per-value field sets, plus all the tickets
+#[derive(Debug, Clone)]
+pub struct Parsed {
+    fields: Vec<BitSet>, // the fields each value is valid for, indexed by value
+    n_fields: usize,
+    rules: Vec<Rule>, // kept as written, only needed to print the input back
+    our_ticket: Ticket,
+    tickets: Vec<Ticket>,
+}
+
+impl fmt::Display for Parsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, [r0, r1]) in &self.rules {
+            let (s0, e0, s1, e1) = (r0.start(), r0.end(), r1.start(), r1.end());
+            writeln!(f, "{}: {}-{} or {}-{}", name, s0, e0, s1, e1)?;
+        }
+        writeln!(f, "\nyour ticket:\n{}", self.our_ticket.iter().join(","))?;
+        writeln!(f, "\nnearby tickets:")?;
+        for ticket in &self.tickets {
+            writeln!(f, "{}", ticket.iter().join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl Parsed {
+    #[inline]
+    fn fields(&self, value: u16) -> Option<&BitSet> {
+        // `None` if the value isn't valid for any field
+        self.fields
+            .get(value as usize)
+            .filter(|fields| !fields.is_empty())
+    }
+}
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed {
+    let mut fields = Vec::<BitSet>::with_capacity(1024);
+    let mut n_fields = 0;
+    let mut rules = Vec::with_capacity(32);
+    for (i, (name, ranges)) in parse_valid_ranges(&mut s).enumerate() {
+        for range in &ranges {
+            if fields.len() <= *range.end() as usize {
+                fields.resize(*range.end() as usize + 1, BitSet::new());
+            }
+            for value in range.clone() {
+                fields[value as usize].insert(i);
+            }
+        }
+        n_fields += 1;
+        rules.push((name.to_str_lossy().into_owned(), ranges));
+    }
+    s = s.skip_past(b':', 1);
+    let our_ticket = parse_ticket(&mut s, n_fields);
+    s = s.skip_past(b':', 1);
+    let mut tickets = Vec::with_capacity(256);
+    while s.len() > 1 {
+        tickets.push(parse_ticket(&mut s, n_fields));
+    }
+    Parsed {
+        fields,
+        n_fields,
+        rules,
+        our_ticket,
+        tickets,
+    }
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.to_string()
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    let mut error_rate = 0u16;
+    for ticket in &s.tickets {
+        for &value in ticket.iter() {
+            if s.fields(value).is_none() {
+                error_rate = error_rate.wrapping_add(value);
+            }
+        }
+    }
+    error_rate
+}
+
+#[inline]
+pub fn part1(mut s: &[u8]) -> u16 {
+    // the tickets aren't needed past this, so their values are checked as they're read
+    let mut valid = [false; 1024];
+    for (_, ranges) in parse_valid_ranges(&mut s) {
+        for range in &ranges {
+            for value in range.clone() {
+                valid.set_at(value as _, true);
+            }
+        }
+    }
+    s = s.skip_past(b':', 1);
+    s = s.skip_past(b':', 1);
+    let mut error_rate = 0u16;
+    while s.len() > 1 {
+        let value: u16 = parse_int_fast(&mut s, 1, 3);
+        if !valid.get_at(value as _) {
+            error_rate = error_rate.wrapping_add(value);
+        }
+    }
+    error_rate
+}
+
+#[inline]
+fn solve2_explained(s: &Parsed, e: &mut impl Explain) -> u64 {
+    let n_fields = s.n_fields;
+    let mut map = vec![BitSet::full(n_fields); n_fields]; // candidate fields for each column
+    let (mut n_valid, mut n_invalid) = (0, 0);
+    for ticket in &s.tickets {
+        if ticket.iter().any(|&value| s.fields(value).is_none()) {
+            n_invalid += 1;
+            continue;
+        }
+        for (candidates, &value) in map.iter_mut().zip(ticket.iter()) {
+            *candidates &= &s.fields[value as usize];
+        }
+        n_valid += 1;
+    }
+    e.step(format_args!(
+        "tickets: {} valid, {} discarded, {} fields",
+        n_valid, n_invalid, n_fields
+    ));
+    let mut tagged_map: Vec<_> = map.into_iter().enumerate().collect();
+    tagged_map.sort_by_key(|(_, candidates)| candidates.len());
+    let mut taken = BitSet::with_capacity(n_fields);
+    let mut answer = 1;
+    for (i, candidates) in &tagged_map {
+        let choices = candidates - &taken;
+        debug_assert_eq!(choices.len(), 1);
+        let k = match choices.first() {
+            Some(k) => k,
+            None => continue, // ambiguous input, nothing left for this column
+        };
+        e.step(format_args!(
+            "column {}: {} candidates, {} taken by earlier columns, field {} left",
+            i,
+            candidates.len(),
+            candidates.len() - choices.len(),
+            k
+        ));
+        if k < 6 {
+            answer *= s.our_ticket[*i] as u64;
+            e.step(format_args!(
+                "departure: field {} in column {}, value {}",
+                k, i, s.our_ticket[*i]
+            ));
+        }
+        taken.insert(k);
+    }
+    answer
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u64 {
+    solve2_explained(s, &mut ())
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u64 {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, u64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub fn explain(s: &[u8]) -> String {
+    let mut trace = Trace::default();
+    solve2_explained(&parse(s), &mut trace);
+    trace.into()
+}
+
+#[test]
+fn test_day16_part1() {
+    assert_eq!(part1(input()), 23044);
+    let s = b"class: 1-3 or 5-7\nrow: 6-11 or 33-44\nseat: 13-40 or 45-50\n\n\
+              your ticket:\n7,1,14\n\n\
+              nearby tickets:\n7,3,47\n40,4,50\n55,2,20\n38,6,12\n";
+    assert_eq!((part1(s), solve1(&parse(s))), (71, 71));
+}
+
+#[test]
+fn test_day16_part2() {
+    assert_eq!(part2(input()), 3765150732757);
+    // more fields than fit inline: column `j` can only be field `j + 1`, wrapping around
+    let mut s = String::new();
+    for i in 0..40 {
+        s += &format!("f{}: {}-{} or 999-999\n", i, 10 + i, 10 + i);
+    }
+    let row = |f: &dyn Fn(usize) -> usize| (0..40).map(f).join(",");
+    s += &format!("\nyour ticket:\n{}\n", row(&|j| 100 + j));
+    s += &format!("\nnearby tickets:\n{}\n", row(&|j| 10 + (j + 1) % 40));
+    s += &format!("{}\n", row(&|j| if j == 3 { 5 } else { 10 + j }));
+    let s = s.as_bytes();
+    assert_eq!(part1(s), 5);
+    assert_eq!(part2(s), 139 * 100 * 101 * 102 * 103 * 104);
+    assert_lossless_round_trip(s, |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day16_explain() {
+    let trace = explain(input());
+    assert!(trace.starts_with("tickets: 190 valid, 55 discarded, 20 fields\n"));
+    assert!(trace.contains("\ncolumn 12: 1 candidates, 0 taken by earlier columns, field 8 left\n"));
+    assert_eq!(trace.matches("\ncolumn ").count(), 20);
+    // the values of the six departure fields multiply up to the answer
+    let departures: Vec<u64> = trace
+        .lines()
+        .filter(|line| line.starts_with("departure: "))
+        .map(|line| line.rsplit(' ').next().unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(departures.len(), 6);
+    assert_eq!(departures.iter().product::<u64>(), part2(input()));
+}
+
+#[test]
+fn test_day16_solve_both() {
+    assert_eq!(solve_both(input()), (23044, 3765150732757));
+}
+
+#[test]
+fn test_day16_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
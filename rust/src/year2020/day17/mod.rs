@@ -1,24 +1,62 @@
 use arrayvec::ArrayVec;
 use rustc_hash::FxHashSet;
 
+use crate::params::{self, parse_value, ParamError};
 use crate::utils::*;
 
-const N_ITER: usize = 6;
+const N_ITER: usize = 6; // the boards are sized for this many cycles at most
+
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub cycles: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { cycles: N_ITER }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        match key {
+            "cycles" => match parse_value(key, value)? {
+                cycles if cycles <= N_ITER => self.cycles = cycles,
+                _ => {
+                    return Err(ParamError::OutOfRange(
+                        key.into(),
+                        value.into(),
+                        format!("at most {}", N_ITER),
+                    ))
+                }
+            },
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![("cycles", self.cycles.to_string())]
+    }
+}
 
 #[inline]
 pub fn input() -> &'static [u8] {
     include_bytes!("input.txt")
 }
 
+// the active cells of the initial board
+pub type Parsed = ArrayVec<[(i32, i32); 256]>;
+
 #[inline]
-fn parse_board(mut s: &[u8]) -> ArrayVec<[(i32, i32); 256]> {
+pub fn parse(mut s: &[u8]) -> Parsed {
     let mut out = ArrayVec::new();
     let width = s.memchr(b'\n');
     let mut row = 0;
     while s.len() > 1 {
         for col in 0..width {
             if s.get_at(col) == b'#' {
-                unsafe { out.push_unchecked((row, col as _)) };
+                out.push_fast((row, col as _));
             }
         }
         row += 1;
@@ -28,7 +66,26 @@ fn parse_board(mut s: &[u8]) -> ArrayVec<[(i32, i32); 256]> {
 }
 
 #[inline]
-pub fn part1(s: &[u8]) -> u64 {
+pub fn unparse(s: &Parsed) -> String {
+    // the smallest board with all the active cells, so any empty rows and columns at the
+    // bottom and on the right are dropped
+    let rows = s.iter().map(|&(row, _)| row + 1).max().unwrap_or(0);
+    let cols = s.iter().map(|&(_, col)| col + 1).max().unwrap_or(0);
+    let mut board = vec![vec![b'.'; cols as usize]; rows as usize];
+    for &(row, col) in s {
+        board[row as usize][col as usize] = b'#';
+    }
+    let board = board.into_iter().map(|row| String::from_utf8(row).unwrap());
+    to_lines(board)
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u64 {
+    solve1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve1_with(s: &Parsed, params: &Params) -> u64 {
     // ones coordinate fits within 0-16, the other two fit within 0-32 => 14 bits
     const D0: usize = 1 << 4;
     const D1: usize = 1 << 5;
@@ -93,7 +150,7 @@ pub fn part1(s: &[u8]) -> u64 {
 
     const CENTER: Offset = ((D0 >> 1) * (D1 * D2)) as Offset;
 
-    for &(x, y) in &parse_board(s) {
+    for &(x, y) in s {
         let x = ((x - 4) as Offset) + ((D1 as Offset) >> 1);
         let y = ((y - 4) as Offset) + ((D2 as Offset) >> 1);
         let id = CENTER + y * (D1 as Offset) + x;
@@ -101,7 +158,7 @@ pub fn part1(s: &[u8]) -> u64 {
         board[0][id as usize] = 1;
     }
 
-    for step in 0..N_ITER {
+    for step in 0..params.cycles.min(N_ITER) {
         touched.clear();
 
         let counts = &mut counts[step];
@@ -113,26 +170,38 @@ pub fn part1(s: &[u8]) -> u64 {
             }
         }
 
-        let next_active = unsafe { active.as_mut_ptr().add(step + 1) };
-        let next_board = unsafe { board.as_mut_ptr().add(step + 1) };
-
-        let board = &board[step];
+        let next_active = &mut active[step + 1];
+        let (board, next_board) = board.split_at_mut(step + 1);
+        let (board, next_board) = (&board[step], &mut next_board[0]);
         for &id in &touched {
             let count = counts.get_at(id as _);
             if count == 3 || (count == 2 && board.get_at(id as _) != 0) {
-                unsafe {
-                    (*next_active).push_unchecked(id);
-                    (*next_board).set_at(id as _, 1);
-                }
+                next_active.push_fast(id);
+                next_board.set_at(id as _, 1);
             }
         }
     }
 
-    active[N_ITER].len() as _
+    active[params.cycles.min(N_ITER)].len() as _
 }
 
 #[inline]
-pub fn part2(s: &[u8]) -> u64 {
+pub fn part1(s: &[u8]) -> u64 {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> u64 {
+    solve1_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u64 {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve2_with(s: &Parsed, params: &Params) -> u64 {
     // two coordinates fit within 0-16, the other two fit within 0-32 => 18 bits
     const D0: usize = 1 << 4;
     const D1: usize = 1 << 4;
@@ -254,7 +323,7 @@ pub fn part2(s: &[u8]) -> u64 {
 
     const CENTER: Offset = ((D0 >> 1) * (D1 * D2 * D3) + (D1 >> 1) * (D2 * D3)) as Offset;
 
-    for &(x, y) in &parse_board(s) {
+    for &(x, y) in s {
         let x = (x - 4) + ((D3 as Offset) >> 1);
         let y = (y - 4) + ((D2 as Offset) >> 1);
         let id = CENTER + y * (D2 as Offset) + x;
@@ -262,7 +331,7 @@ pub fn part2(s: &[u8]) -> u64 {
         board[0][id as usize] = 1;
     }
 
-    for step in 0..N_ITER {
+    for step in 0..params.cycles.min(N_ITER) {
         touched.clear();
 
         let counts = &mut counts[step];
@@ -274,22 +343,35 @@ pub fn part2(s: &[u8]) -> u64 {
             }
         }
 
-        let next_active = unsafe { active.as_mut_ptr().add(step + 1) };
-        let next_board = unsafe { board.as_mut_ptr().add(step + 1) };
-
-        let board = &board[step];
+        let next_active = &mut active[step + 1];
+        let (board, next_board) = board.split_at_mut(step + 1);
+        let (board, next_board) = (&board[step], &mut next_board[0]);
         for &id in &touched {
             let count = counts.get_at(id as _);
             if count == 3 || (count == 2 && board.get_at(id as _) != 0) {
-                unsafe {
-                    (*next_active).push_unchecked(id);
-                    (*next_board).set_at(id as _, 1);
-                }
+                next_active.push_fast(id);
+                next_board.set_at(id as _, 1);
             }
         }
     }
 
-    active[N_ITER].len() as _
+    active[params.cycles.min(N_ITER)].len() as _
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u64 {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> u64 {
+    solve2_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u64, u64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
 }
 
 #[test]
@@ -301,3 +383,25 @@ fn test_day16_part1() {
 fn test_day16_part2() {
     assert_eq!(part2(input()), 1180);
 }
+
+#[test]
+fn test_day17_params() {
+    let s = b".#.\n..#\n###\n";
+    let params = Params { cycles: 1 };
+    assert_eq!(part1_with(s, &params), 11);
+    assert_eq!(part2_with(s, &params), 29);
+    assert_eq!(part1_with(s, &Params { cycles: 0 }), 5);
+    let err = params::Params::set(&mut Params::default(), "cycles", "7").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value for cycles: 7 (must be at most 6)"
+    );
+}
+
+#[test]
+fn test_day17_unparse() {
+    assert_eq!(unparse(&parse(b".#.\n..#\n###\n")), ".#.\n..#\n###\n");
+    assert_eq!(unparse(&parse(b".#..\n....\n")), ".#\n");
+    let s = assert_round_trip(input(), |s| unparse(&parse(s)));
+    assert_eq!(parse(s.as_bytes()), parse(input()));
+}
@@ -1,3 +1,5 @@
+use std::io::{self, BufRead};
+
 use crate::utils::*;
 
 #[inline]
@@ -70,6 +72,44 @@ pub fn part2(mut s: &[u8]) -> u64 {
     total
 }
 
+// expressions are evaluated while parsing, with different precedence rules
+pub type Parsed<'a> = &'a [u8];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    s
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u64 {
+    part1(s)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u64 {
+    part2(s)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u64, u64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    let mut total = 0;
+    for_each_line(r, |mut line| total += parse_expr_no_precedence(&mut line))?;
+    Ok(total)
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<u64> {
+    let mut total = 0;
+    for_each_line(r, |mut line| total += parse_expr_with_precedence(&mut line))?;
+    Ok(total)
+}
+
 #[test]
 fn test_day18_part1() {
     assert_eq!(part1(input()), 2743012121210);
@@ -79,3 +119,16 @@ fn test_day18_part1() {
 fn test_day18_part2() {
     assert_eq!(part2(input()), 65658760783597);
 }
+
+#[test]
+fn test_day18_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        2743012121210
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        65658760783597
+    );
+}
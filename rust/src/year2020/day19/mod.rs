@@ -1,8 +1,10 @@
+use std::fmt;
 use std::ops::Index;
 
 use crate::utils::*;
 
 use arrayvec::ArrayVec;
+use bstr::ByteSlice;
 
 const MAX_RULES: usize = 256;
 
@@ -10,7 +12,7 @@ type Id = u8;
 type Match<'a> = Option<&'a [u8]>;
 type CharMap = [u8; 256];
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Term {
     AnyChar,
     Pair(u8, u8),
@@ -21,7 +23,7 @@ enum Term {
     Just(Id),
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Rule {
     Either(Term, Term),
     Just(Term),
@@ -34,7 +36,7 @@ impl Index<Id> for Rules {
     type Output = Rule;
 
     fn index(&self, index: u8) -> &Self::Output {
-        unsafe { self.0.get_unchecked(index as usize) }
+        self.0.get_ref_at(index as usize)
     }
 }
 
@@ -127,18 +129,15 @@ fn parse_rules(s: &mut &[u8]) -> Rules {
 
     let mut rules_and_ids = ArrayVec::<[_; MAX_RULES]>::new();
     while s.get_first() != b'\n' {
-        unsafe { rules_and_ids.push_unchecked(parse_rule(s, &map)) };
+        rules_and_ids.push_fast(parse_rule(s, &map));
     }
     rules_and_ids.sort_unstable_by_key(|&(id, _)| id);
 
-    let mut rules = ArrayVec::new();
-    unsafe { rules.set_len(rules_and_ids.len()) };
-    for (i, &(id, rule)) in rules_and_ids.iter().enumerate() {
+    let rules = rules_and_ids.iter().enumerate().map(|(i, &(id, rule))| {
         debug_assert_eq!(i as Id, id);
-        rules[i] = rule;
-    }
-
-    Rules(rules)
+        rule
+    });
+    Rules(rules.collect())
 }
 
 impl Rules {
@@ -291,12 +290,73 @@ fn compute_pattern_lengths(rules: &Rules) -> ArrayVec<[usize; MAX_RULES]> {
     counts.into_iter().map(|c| c[0] as usize).collect()
 }
 
+// the rules with the length of the pattern each one matches, and the unparsed messages
+pub struct Parsed<'a> {
+    rules: Rules,
+    lengths: ArrayVec<[usize; MAX_RULES]>,
+    messages: &'a [u8],
+}
+
+impl fmt::Display for Parsed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the char rules are inlined when parsing, so a reference to a char is printed as
+        // the first rule matching it; the rules come out sorted by id
+        let rules = &self.rules.0;
+        let literal = |c: u8| {
+            let is_literal = |rule: &Rule| *rule == Rule::Just(Term::Char(c));
+            rules.iter().position(is_literal).unwrap()
+        };
+        let mut chars: Vec<_> = rules
+            .iter()
+            .filter_map(|rule| match rule {
+                Rule::Just(Term::Char(c)) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        chars.sort_unstable();
+        chars.dedup();
+        let term = |term: &Term| match *term {
+            Term::AnyChar => format!("{} | {}", literal(chars[0]), literal(chars[1])),
+            Term::Pair(c1, c2) => format!("{} {}", literal(c1), literal(c2)),
+            Term::Prepend(id, c) => format!("{} {}", literal(c), id),
+            Term::Append(id, c) => format!("{} {}", id, literal(c)),
+            Term::Concat(id1, id2) => format!("{} {}", id1, id2),
+            Term::Char(c) => literal(c).to_string(),
+            Term::Just(id) => id.to_string(),
+        };
+        for (id, rule) in rules.iter().enumerate() {
+            match rule {
+                Rule::Just(Term::Char(c)) if literal(*c) == id => {
+                    writeln!(f, "{}: \"{}\"", id, *c as char)?
+                }
+                Rule::Just(t) => writeln!(f, "{}: {}", id, term(t))?,
+                Rule::Either(t1, t2) => writeln!(f, "{}: {} | {}", id, term(t1), term(t2))?,
+            }
+        }
+        write!(f, "{}", self.messages.as_bstr()) // starting with the blank line
+    }
+}
+
 #[inline]
-pub fn part1(mut s: &[u8]) -> u16 {
+pub fn parse(mut s: &[u8]) -> Parsed<'_> {
     let rules = parse_rules(&mut s);
     let lengths = compute_pattern_lengths(&rules);
-    let root_len = lengths[0];
-    parse_inputs(s)
+    Parsed {
+        rules,
+        lengths,
+        messages: s,
+    }
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.to_string()
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    let (rules, root_len) = (&s.rules, s.lengths[0]);
+    parse_inputs(s.messages)
         .map(|line| {
             if line.len() != root_len {
                 0
@@ -311,10 +371,14 @@ pub fn part1(mut s: &[u8]) -> u16 {
 }
 
 #[inline]
-pub fn part2(mut s: &[u8]) -> u16 {
-    let rules = parse_rules(&mut s);
-    let lengths = compute_pattern_lengths(&rules);
-    parse_inputs(s)
+pub fn part1(s: &[u8]) -> u16 {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u16 {
+    let (rules, lengths) = (&s.rules, &s.lengths);
+    parse_inputs(s.messages)
         .map(|mut line| {
             let (mut n_42, mut n_31) = (0, 0);
             for (n, id) in &mut [(&mut n_42, 42), (&mut n_31, 31)] {
@@ -334,6 +398,17 @@ pub fn part2(mut s: &[u8]) -> u16 {
         .sum()
 }
 
+#[inline]
+pub fn part2(s: &[u8]) -> u16 {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, u16) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
 #[test]
 fn test_day19_part1() {
     assert_eq!(part1(input()), 142);
@@ -343,3 +418,19 @@ fn test_day19_part1() {
 fn test_day19_part2() {
     assert_eq!(part2(input()), 294);
 }
+
+#[test]
+fn test_day19_unparse() {
+    let (s, parsed) = (unparse(&parse(input())), parse(input()));
+    let reparsed = parse(s.as_bytes());
+    assert_eq!(reparsed.rules.0, parsed.rules.0);
+    assert_eq!(reparsed.messages, parsed.messages);
+    assert_round_trip(input(), |s| unparse(&parse(s)));
+    // rule 6 is a second "a", so it comes back as a reference to rule 4
+    let s = b"0: 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 6 5 | 5 6\n4: \"a\"\n5: \"b\"\n6: \"a\"\n\n\
+              aaabb\nabbbb\naaaab\naaabba\n";
+    let text = assert_round_trip(s, |s| unparse(&parse(s)));
+    assert!(text.starts_with("0: 1 5\n1: 2 3 | 3 2\n2: 4 4 | 5 5\n3: 4 5 | 5 4\n"));
+    assert!(text.contains("\n6: 4\n\n"));
+    assert_eq!((part1(s), part1(text.as_bytes())), (2, 2));
+}
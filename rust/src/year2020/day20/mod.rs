@@ -1,8 +1,11 @@
+use std::fmt;
 use std::mem;
 
+use crate::explain::{Explain, Trace};
 use crate::utils::*;
 
 use arrayvec::ArrayVec;
+use bstr::ByteSlice;
 
 const WIDTH: usize = 10;
 const SIDE: usize = 12;
@@ -24,7 +27,7 @@ fn flip_edge(edge: Edge) -> Edge {
     edge.reverse_bits() >> (8 * mem::size_of::<Edge>() - WIDTH)
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 struct Tile {
     id: u16,
     edges: [u16; 8], // edges 0 2 4 6 are normal, 1 3 5 7 are flipped
@@ -77,6 +80,26 @@ impl Tile {
     }
 }
 
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // only the edges are kept, so the inside of the tile comes back empty
+        let [up, _, right, _, down, _, left, _] = self.edges;
+        let bit = |edge: u16, i: usize| if edge >> i & 1 != 0 { '#' } else { '.' };
+        writeln!(f, "Tile {}:", self.id)?;
+        for y in 0..WIDTH {
+            let row = (0..WIDTH).map(|x| match (y, x) {
+                (0, _) => bit(up, x),
+                (y, _) if y == WIDTH - 1 => bit(down, WIDTH - 1 - x),
+                (_, 0) => bit(left, WIDTH - 1 - y),
+                (_, x) if x == WIDTH - 1 => bit(right, y),
+                _ => '.',
+            });
+            writeln!(f, "{}", row.collect::<String>())?;
+        }
+        writeln!(f)
+    }
+}
+
 #[inline]
 fn parse_tiles(mut s: &[u8]) -> ArrayVec<[Tile; N]> {
     let mut tiles = ArrayVec::new();
@@ -176,7 +199,7 @@ fn find_boundary(edge_map: &EdgeMap) -> [usize; 4 * (SIDE - 1)] {
 }
 
 #[inline]
-fn build_image(tiles: &[Tile], edge_map: &EdgeMap) -> Image {
+fn build_image(tiles: &[Tile], edge_map: &EdgeMap, e: &mut impl Explain) -> Image {
     // rotation = which edge faces top (this already accounts for flipping)
     let mut image: Image = [[(0, 0); SIDE]; SIDE];
 
@@ -188,6 +211,12 @@ fn build_image(tiles: &[Tile], edge_map: &EdgeMap) -> Image {
         .next()
         .unwrap();
     image[0][0] = (tile_num, rotation);
+    e.step(format_args!(
+        "place: tile {} goes top-left with edge {} on top{}",
+        tiles[tile_num].id,
+        rotation,
+        if rotation % 2 == 1 { " (flipped)" } else { "" }
+    ));
 
     // place the top row (link from left to right)
     for j in 1..SIDE {
@@ -307,11 +336,11 @@ fn get_monster_masks() -> [Mask; 8] {
 }
 
 #[derive(Debug, Copy, Clone)]
-struct Cursor<T> {
-    ptr: *const T,
+struct Cursor<'a, T> {
+    rest: &'a [T],
 }
 
-impl<T: Copy> Cursor<T> {
+impl<T: Copy> Cursor<'_, T> {
     #[inline(always)]
     pub fn step(&mut self) {
         self.jump(1);
@@ -319,16 +348,16 @@ impl<T: Copy> Cursor<T> {
 
     #[inline(always)]
     pub fn jump(&mut self, n: usize) {
-        unsafe { self.ptr = self.ptr.add(n) };
+        self.rest = self.rest.advance(n);
     }
 
     #[inline(always)]
     pub fn get(&mut self, n: usize) -> T {
-        unsafe { *self.ptr.add(n) }
+        self.rest.get_at(n)
     }
 }
 
-impl Cursor<bool> {
+impl Cursor<'_, bool> {
     #[inline(always)]
     pub fn check_mask(&mut self, offsets: &[usize]) -> bool {
         for &offset in offsets {
@@ -341,11 +370,9 @@ impl Cursor<bool> {
 }
 
 #[inline]
-fn count_monsters(bitmap: &Bitmap, masks: &[Mask; 8]) -> u16 {
+fn count_monsters(bitmap: &Bitmap, masks: &[Mask; 8], e: &mut impl Explain) -> u16 {
     let (mut x0, mut y0, mut rotation) = (0, 0, 0);
-    let mut cursor = Cursor {
-        ptr: bitmap.as_ptr(),
-    };
+    let mut cursor = Cursor { rest: &bitmap[..] };
 
     // first, we need to figure mask rotation - find just one matching instance
     'outer: for y in 0..N_PIXELS {
@@ -367,6 +394,10 @@ fn count_monsters(bitmap: &Bitmap, masks: &[Mask; 8]) -> u16 {
 
     let mut count = 1;
     let mask = &masks[rotation];
+    e.step(format_args!(
+        "monster: first one at x = {}, y = {} in orientation {}",
+        x0, y0, rotation
+    ));
 
     // finish the unfinished row
     for _ in (x0 + 1)..(N_PIXELS - mask.width) {
@@ -396,11 +427,46 @@ pub fn input() -> &'static [u8] {
     include_bytes!("input.txt")
 }
 
+// the tiles and their edges; the raw tiles are kept around to assemble the bitmap from
+pub struct Parsed<'a> {
+    tiles: ArrayVec<[Tile; N]>,
+    edge_map: EdgeMap,
+    raw: &'a [u8],
+}
+
+impl fmt::Display for Parsed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw.as_bstr())
+    }
+}
+
 #[inline]
-pub fn part1(s: &[u8]) -> u64 {
+pub fn parse(s: &[u8]) -> Parsed<'_> {
     let tiles = parse_tiles(s);
     let edge_map = build_edge_map(&tiles);
-    find_boundary(&edge_map)
+    Parsed {
+        tiles,
+        edge_map,
+        raw: s,
+    }
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.to_string()
+}
+
+#[inline]
+fn solve1_explained(s: &Parsed, e: &mut impl Explain) -> u64 {
+    let tiles = &s.tiles;
+    let corners = find_boundary(&s.edge_map);
+    for &tile_num in &corners[..4] {
+        e.step(format_args!(
+            "corner: tile {} has two unmatched sides",
+            tiles[tile_num].id
+        ));
+    }
+    corners
         .iter()
         .take(4)
         .map(|&id| tiles[id].id as u64)
@@ -408,14 +474,52 @@ pub fn part1(s: &[u8]) -> u64 {
 }
 
 #[inline]
-pub fn part2(s: &[u8]) -> u16 {
-    let tiles = parse_tiles(s);
-    let edge_map = build_edge_map(&tiles);
-    let image = build_image(&tiles, &edge_map);
-    let bitmap = parse_bitmap(s, &image);
+pub fn solve1(s: &Parsed) -> u64 {
+    solve1_explained(s, &mut ())
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u64 {
+    solve1(&parse(s))
+}
+
+#[inline]
+fn solve2_explained(s: &Parsed, e: &mut impl Explain) -> u16 {
+    let image = build_image(&s.tiles, &s.edge_map, e);
+    let bitmap = parse_bitmap(s.raw, &image);
     let masks = get_monster_masks();
-    let n_monsters = count_monsters(&bitmap, &masks);
-    bitmap.iter().map(|&x| x as u16).sum::<u16>() - (masks[0].offsets.len() as u16 * n_monsters)
+    let n_monsters = count_monsters(&bitmap, &masks, e);
+    let n_waves = bitmap.iter().map(|&x| x as u16).sum::<u16>();
+    let n_covered = masks[0].offsets.len() as u16 * n_monsters;
+    e.step(format_args!(
+        "roughness: {} monsters cover {} of {} waves",
+        n_monsters, n_covered, n_waves
+    ));
+    n_waves - n_covered
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u16 {
+    solve2_explained(s, &mut ())
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u16 {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u64, u16) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub fn explain(s: &[u8]) -> String {
+    let s = parse(s);
+    let mut trace = Trace::default();
+    solve1_explained(&s, &mut trace);
+    solve2_explained(&s, &mut trace);
+    trace.into()
 }
 
 #[test]
@@ -427,3 +531,51 @@ fn test_day20_part1() {
 fn test_day20_part2() {
     assert_eq!(part2(input()), 1649);
 }
+
+#[test]
+fn test_day20_explain() {
+    let trace = explain(input());
+    let corners = trace.lines().filter_map(|line| {
+        let line = line.strip_prefix("corner: tile ")?;
+        line.strip_suffix(" has two unmatched sides")?
+            .parse::<u64>()
+            .ok()
+    });
+    assert_eq!(corners.product::<u64>(), part1(input()));
+    assert!(trace.contains("\nplace: tile 1499 goes top-left with edge 2 on top\n"));
+    assert!(trace.ends_with("roughness: 18 monsters cover 270 of 1919 waves\n"));
+    // 1919 - 270 = 1649
+}
+
+#[test]
+fn test_day20_solve_both() {
+    assert_eq!(solve_both(input()), (14129524957217, 1649));
+}
+
+#[test]
+fn test_day20_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+    for tile in &parse(input()).tiles {
+        let s = tile.to_string();
+        assert_eq!(Tile::parse(&mut s.as_bytes()), *tile);
+    }
+    // a tile on its own only keeps its edges, so the inside comes back empty
+    let s = &input()[..input().find(b"\n\n").unwrap() + 2];
+    let text = assert_round_trip(s, |s| Tile::parse(&mut &s[..]).to_string());
+    let (rows, text_rows): (Vec<_>, Vec<_>) = (
+        s.trim_end().lines().collect(),
+        text.trim_end().lines().collect(),
+    );
+    assert_eq!(rows.len(), text_rows.len());
+    for (y, (row, text_row)) in rows.iter().zip(&text_rows).enumerate() {
+        let text_row = text_row.as_bytes();
+        if y <= 1 || y == rows.len() - 1 {
+            assert_eq!(text_row, *row); // the id and the top and bottom edges
+        } else {
+            let last = row.len() - 1;
+            assert_eq!((text_row[0], text_row[last]), (row[0], row[last]));
+            assert!(text_row[1..last].iter().all(|&c| c == b'.'));
+        }
+    }
+    assert_ne!(text.as_bytes(), s); // the input's tiles aren't hollow
+}
@@ -0,0 +1,261 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::from_utf8;
+
+use bstr::ByteSlice;
+use itertools::Itertools;
+use rustc_hash::FxHashMap;
+
+use crate::explain::{Explain, Trace};
+use crate::utils::*;
+
+#[derive(Debug, Clone, Default)]
+struct Food {
+    ingredients: BitSet,
+    allergens: BitSet,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Problem<'a> {
+    ingredients: Vec<&'a [u8]>,
+    allergens: Vec<&'a [u8]>,
+    foods: Vec<Food>,
+}
+
+#[inline]
+fn intern<'a>(
+    map: &mut FxHashMap<&'a [u8], usize>,
+    names: &mut Vec<&'a [u8]>,
+    name: &'a [u8],
+) -> usize {
+    // index of the name, assigning the next free one if it's new
+    *map.entry(name).or_insert_with(|| {
+        names.push(name);
+        names.len() - 1
+    })
+}
+
+impl<'a> Problem<'a> {
+    pub fn parse(mut s: &'a [u8]) -> Self {
+        let mut ingredient_map = FxHashMap::with_capacity_and_hasher(256, Default::default());
+        let mut allergen_map = FxHashMap::with_capacity_and_hasher(8, Default::default());
+
+        let mut problem = Self::default();
+
+        while s.len() > 1 {
+            let mut food = Food::default();
+
+            while s.get_first() != b'(' {
+                let mut pos = 2;
+                while s.get_at(pos) != b' ' {
+                    pos += 1;
+                }
+                let ingredient = &s[..pos];
+                let n = intern(&mut ingredient_map, &mut problem.ingredients, ingredient);
+                food.ingredients.insert(n);
+                s = s.advance(pos + 1);
+            }
+
+            s = s.advance(10);
+            while s.get_first() != b'\n' {
+                let pos = s.memchr2(b',', b')');
+                let allergen = &s[..pos];
+                let n = intern(&mut allergen_map, &mut problem.allergens, allergen);
+                food.allergens.insert(n);
+                s = s.advance(pos + 1 + (s.get_at(pos) == b',') as usize);
+            }
+            s = s.advance(1);
+
+            problem.foods.push(food);
+        }
+
+        problem
+    }
+
+    pub fn find_overlaps(&self) -> Vec<BitSet> {
+        // for each allergen, the ingredients present in all foods listing it
+        let all = BitSet::full(self.ingredients.len());
+        let mut out = vec![all; self.allergens.len()];
+        for food in &self.foods {
+            for allergen in &food.allergens {
+                out[allergen] &= &food.ingredients;
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Problem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the ingredients and allergens of each food come out in the order they were first seen
+        for food in &self.foods {
+            let ingredients = food
+                .ingredients
+                .iter()
+                .map(|i| self.ingredients[i].as_bstr());
+            let allergens = food.allergens.iter().map(|i| self.allergens[i].as_bstr());
+            let (ingredients, allergens) = (ingredients.format(" "), allergens.format(", "));
+            writeln!(f, "{} (contains {})", ingredients, allergens)?;
+        }
+        Ok(())
+    }
+}
+
+// formats the names of the ingredients in a mask lazily, so it costs nothing unless explained
+struct Names<'a>(&'a [&'a [u8]], &'a BitSet);
+
+impl fmt::Display for Names<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = self.1.iter().map(|i| self.0[i].as_bstr());
+        write!(f, "{}", names.format(","))
+    }
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+pub type Parsed<'a> = Problem<'a>;
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed<'_> {
+    Problem::parse(s)
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.to_string()
+}
+
+#[inline]
+fn solve1_explained(problem: &Parsed, e: &mut impl Explain) -> u16 {
+    let overlaps = problem.find_overlaps();
+    for (&allergen, overlap) in problem.allergens.iter().zip(&overlaps) {
+        e.step(format_args!(
+            "overlap: {} in all foods listing it, {} candidates: {}",
+            allergen.as_bstr(),
+            overlap.len(),
+            Names(&problem.ingredients, overlap)
+        ));
+    }
+    let mut maybe_allergens = BitSet::new();
+    for overlap in &overlaps {
+        maybe_allergens |= overlap;
+    }
+    e.step(format_args!(
+        "safe: {} of {} ingredients are in no overlap",
+        problem.ingredients.len() - maybe_allergens.len(),
+        problem.ingredients.len()
+    ));
+    problem
+        .foods
+        .iter()
+        .map(|food| (&food.ingredients - &maybe_allergens).len() as u16)
+        .sum()
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u16 {
+    solve1_explained(s, &mut ())
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u16 {
+    solve1(&parse(s))
+}
+
+#[inline]
+fn solve2_explained(problem: &Parsed, e: &mut impl Explain) -> String {
+    let mut ingredients = Vec::new();
+
+    let mut overlaps: Vec<_> = problem
+        .allergens
+        .iter()
+        .copied()
+        .zip(problem.find_overlaps())
+        .collect();
+    overlaps.sort_unstable_by_key(|(_, o)| Reverse(o.len()));
+
+    while let Some((allergen, mask)) = overlaps.pop() {
+        debug_assert_eq!(mask.len(), 1);
+        e.step(format_args!(
+            "peel: {} has a single candidate left: {}",
+            allergen.as_bstr(),
+            Names(&problem.ingredients, &mask)
+        ));
+        ingredients.push((allergen, problem.ingredients[mask.first().unwrap()]));
+        for (_, overlap) in &mut overlaps {
+            *overlap -= &mask;
+        }
+        overlaps.sort_unstable_by_key(|(_, o)| Reverse(o.len()));
+    }
+
+    ingredients.sort_unstable();
+    ingredients
+        .iter()
+        .map(|&(_, ingredient)| from_utf8(ingredient).unwrap())
+        .join(",")
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> String {
+    solve2_explained(s, &mut ())
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> String {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u16, String) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+pub fn explain(s: &[u8]) -> String {
+    let s = parse(s);
+    let mut trace = Trace::default();
+    solve1_explained(&s, &mut trace);
+    solve2_explained(&s, &mut trace);
+    trace.into()
+}
+
+#[test]
+fn test_day21_part1() {
+    assert_eq!(part1(input()), 1679);
+}
+
+#[test]
+fn test_day21_part2() {
+    assert_eq!(
+        part2(input()),
+        "lmxt,rggkbpj,mxf,gpxmf,nmtzlj,dlkxsxg,fvqg,dxzq"
+    );
+}
+
+#[test]
+fn test_day21_explain() {
+    let trace = explain(input());
+    assert!(trace.starts_with("overlap: fish in all foods listing it, 2 candidates: dlkxsxg,mxf\n"));
+    assert!(trace.contains("\nsafe: 192 of 200 ingredients are in no overlap\n"));
+    // the peeled allergens, sorted, give the canonical list of part 2
+    let mut peeled: Vec<_> = trace
+        .lines()
+        .filter_map(|line| line.strip_prefix("peel: "))
+        .filter_map(|line| line.split_once(" has a single candidate left: "))
+        .collect();
+    peeled.sort_unstable();
+    let names: Vec<_> = peeled.iter().map(|&(_, name)| name).collect();
+    assert_eq!(names.join(","), part2(input()));
+}
+
+#[test]
+fn test_day21_unparse() {
+    let s = b"mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\n\
+              mxmxvkd trh fvjkl sbzzf (contains dairy)\n";
+    assert_lossless_round_trip(s, |s| unparse(&parse(s)));
+    let s = assert_round_trip(input(), |s| unparse(&parse(s)));
+    assert_eq!(part1(s.as_bytes()), 1679);
+}
@@ -13,28 +13,39 @@ type Deck = VecDeque<Card>;
 fn parse_deck(s: &mut &[u8]) -> Deck {
     let mut deck = VecDeque::with_capacity(64);
     *s = s.advance(10);
-    while s.get_first() != b'\n' && !s.is_empty() {
+    while !s.is_empty() && s.get_first() != b'\n' {
         deck.push_back(parse_int_fast(s, 1, 2));
     }
-    *s = s.advance(1);
+    if !s.is_empty() {
+        *s = s.advance(1); // the blank line between the decks
+    }
     deck
 }
 
+// the two starting decks
+pub type Parsed = (Deck, Deck);
+
 #[inline]
-fn parse_decks(mut s: &[u8]) -> (Deck, Deck) {
+pub fn parse(mut s: &[u8]) -> Parsed {
     let deck1 = parse_deck(&mut s);
     let deck2 = parse_deck(&mut s);
     (deck1, deck2)
 }
 
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    let (deck1, deck2) = (to_lines(&s.0), to_lines(&s.1));
+    format!("Player 1:\n{}\nPlayer 2:\n{}", deck1, deck2)
+}
+
 #[inline]
 pub fn input() -> &'static [u8] {
     include_bytes!("input.txt")
 }
 
 #[inline]
-pub fn part1(s: &[u8]) -> usize {
-    let (mut deck1, mut deck2) = parse_decks(s);
+pub fn solve1(s: &Parsed) -> usize {
+    let (mut deck1, mut deck2) = s.clone();
     while !deck1.is_empty() && !deck2.is_empty() {
         for _ in 0..deck1.len().min(deck2.len()) {
             let c1 = deck1.pop_front().unwrap();
@@ -57,6 +68,11 @@ pub fn part1(s: &[u8]) -> usize {
         .sum()
 }
 
+#[inline]
+pub fn part1(s: &[u8]) -> usize {
+    solve1(&parse(s))
+}
+
 #[ctor::ctor]
 static TRUNCATE_MASKS: [Deck512; 64] = {
     let mut masks = [Deck512::default(); 64];
@@ -87,25 +103,22 @@ impl Deck512 {
     #[inline]
     pub fn pop(&mut self) {
         // remove top card
-        unsafe {
-            self.write_u8x64(shuffle!(
-                self.read_u8x64(),
-                [
-                    1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
-                    23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42,
-                    43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62,
-                    63, 0
-                ]
-            ));
-            self.as_bytes_mut()[63] = 0;
-        }
+        self.write_u8x64(shuffle!(
+            self.read_u8x64(),
+            [
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+                24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44,
+                45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 0
+            ]
+        ));
+        self.as_bytes_mut()[63] = 0;
     }
 
     #[inline]
     pub fn truncate(&mut self, len: usize) {
         // leave only `len` cards
         let mask = TRUNCATE_MASKS[len];
-        unsafe { self.write_u8x64(self.read_u8x64() & mask.read_u8x64()) };
+        self.write_u8x64(self.read_u8x64() & mask.read_u8x64());
     }
 
     #[inline]
@@ -131,19 +144,19 @@ impl Deck512 {
     }
 
     #[inline]
-    pub unsafe fn read_u8x64(&self) -> u8x64 {
-        u8x64::from_slice_aligned_unchecked(self.as_bytes())
+    pub fn read_u8x64(&self) -> u8x64 {
+        read_lane(self.as_bytes(), 0)
     }
 
     #[inline]
-    pub unsafe fn write_u8x64(&mut self, reg: u8x64) {
-        reg.write_to_slice_aligned_unchecked(self.as_bytes_mut())
+    pub fn write_u8x64(&mut self, reg: u8x64) {
+        write_lane(self.as_bytes_mut(), 0, reg)
     }
 
     #[inline]
     pub fn max(&self) -> Card {
         // find the max card
-        unsafe { self.read_u8x64().max_element() }
+        self.read_u8x64().max_element()
     }
 }
 
@@ -293,8 +306,8 @@ impl FastGame {
 }
 
 #[inline]
-pub fn part2(s: &[u8]) -> usize {
-    let (deck1, deck2) = parse_decks(s);
+pub fn solve2(s: &Parsed) -> usize {
+    let (deck1, deck2) = s;
     let mut game = FastGame::new(
         FastDeck::new(deck1.iter().copied()),
         FastDeck::new(deck2.iter().copied()),
@@ -303,6 +316,17 @@ pub fn part2(s: &[u8]) -> usize {
     game.decks[winner_is_1 as usize].score()
 }
 
+#[inline]
+pub fn part2(s: &[u8]) -> usize {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (usize, usize) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
 #[test]
 fn test_day22_part1() {
     assert_eq!(part1(input()), 32083);
@@ -312,3 +336,8 @@ fn test_day22_part1() {
 fn test_day22_part2() {
     assert_eq!(part2(input()), 35495);
 }
+
+#[test]
+fn test_day22_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
@@ -0,0 +1,290 @@
+use crate::params::{self, parse_value, ParamError};
+use crate::progress::{chunked, Cancelled, Progress};
+use crate::utils::*;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub moves1: usize,
+    pub moves2: usize,
+    pub cups: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            moves1: 100,
+            moves2: 10_000_000,
+            cups: 1_000_000,
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        match key {
+            // shorthand for setting the number of moves in both parts
+            "moves" => {
+                self.moves1 = parse_value(key, value)?;
+                self.moves2 = self.moves1;
+            }
+            "moves1" => self.moves1 = parse_value(key, value)?,
+            "moves2" => self.moves2 = parse_value(key, value)?,
+            "cups" => match parse_value(key, value)? {
+                // the labels from the input must fit, and so must the largest label
+                cups if (9..=Ix::MAX as usize).contains(&cups) => self.cups = cups,
+                _ => {
+                    return Err(ParamError::OutOfRange(
+                        key.into(),
+                        value.into(),
+                        format!("between 9 and {}", Ix::MAX),
+                    ))
+                }
+            },
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("moves1", self.moves1.to_string()),
+            ("moves2", self.moves2.to_string()),
+            ("cups", self.cups.to_string()),
+        ]
+    }
+}
+
+// the labels of the cups, clockwise
+pub type Parsed = [u8; 9];
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed {
+    let mut x = [0; 9];
+    assert_eq!(s.len(), 9);
+    for i in 0..9 {
+        x[i] = s.get_digit_at(i);
+    }
+    x
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    s.iter().map(|&x| (b'0' + x) as char).collect()
+}
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> u64 {
+    part1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], params: &Params) -> u64 {
+    solve1_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> u64 {
+    solve1_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve1_with(s: &Parsed, params: &Params) -> u64 {
+    let mut x = *s;
+    const N: usize = 9;
+    let n = N as u8;
+    for _ in 0..params.moves1 {
+        let v = x[0];
+        let (v1, v2, v3) = (x[1], x[2], x[3]); // labels of picked cups
+        let mut z = v; // destination cup label
+        while {
+            z = n - (n - (z - 1)) % n;
+            z == v1 || z == v2 || z == v3 // ensure it doesn't coincide with taken cups
+        } {}
+        let j = (0..N - 4).position(|j| x[j + 4] == z).unwrap(); // d/c *new* position
+        for i in 0..=j {
+            x[i] = x[i + 4]; // place the cups from next to picked up to including destination
+        }
+        for i in j + 5..N {
+            x[i - 1] = x[i]; // place the cups at the end
+        }
+        x[j + 1] = v1;
+        x[j + 2] = v2;
+        x[j + 3] = v3;
+        x[N - 1] = v;
+    }
+    let i = x.iter().position(|&x| x == 1).unwrap();
+    x.rotate_left(i);
+    x[1..]
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &x)| 10u64.pow(i as _) * (x as u64))
+        .sum()
+}
+
+type Ix = u32;
+
+fn make_list(head: impl AsRef<[u8]>, n: usize) -> Vec<Ix> {
+    let head = head.as_ref().iter().map(|&x| x as Ix).collect::<Vec<_>>();
+    let k = head.len();
+    assert!(k > 0 && n >= k);
+    let mut sorted = head.to_vec();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (1..=k as Ix).collect::<Vec<_>>());
+
+    let mut next = vec![0 as Ix; n + 1]; // zero is an empty node
+    for i in 0..k - 1 {
+        next[head[i] as usize] = head[i + 1] as Ix;
+    }
+    if n == k {
+        next[head[k - 1] as usize] = head[0]; // no extra cups, the circle closes right away
+        return next;
+    }
+    next[head[k - 1] as usize] = (k + 1) as Ix;
+    for i in k + 1..=n - 1 {
+        next[i as usize] = (i + 1) as Ix;
+    }
+    next[n as usize] = (head[0]) as Ix;
+    next
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> u64 {
+    part2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> u64 {
+    solve2_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> u64 {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve2_with(x: &Parsed, params: &Params) -> u64 {
+    match solve2_with_progress(x, params, &mut ()) {
+        Ok(answer) => answer,
+        Err(Cancelled) => unreachable!(),
+    }
+}
+
+#[inline]
+pub fn part2_with_progress<P: Progress + ?Sized>(
+    s: &[u8],
+    params: &Params,
+    progress: &mut P,
+) -> Result<u64, Cancelled> {
+    solve2_with_progress(&parse(s), params, progress)
+}
+
+#[inline]
+pub fn solve2_with_progress<P: Progress + ?Sized>(
+    x: &Parsed,
+    params: &Params,
+    progress: &mut P,
+) -> Result<u64, Cancelled> {
+    let n_cups = params.cups;
+
+    let mut next = make_list(x, n_cups);
+    let mut current = x[0] as usize;
+
+    chunked(params.moves2, progress, |moves| {
+        for _ in moves {
+            // find the three picked cups
+            let x1 = next.get_at(current) as usize;
+            let x2 = next.get_at(x1) as usize;
+            let x3 = next.get_at(x2) as usize;
+
+            // find the destination cup
+            let mut dest = current - 1;
+            if dest == 0 {
+                dest = n_cups;
+            }
+            if dest == x1 || dest == x2 || dest == x3 {
+                for _ in 0..3 {
+                    dest -= 1;
+                    if dest == 0 {
+                        dest = n_cups;
+                    }
+                    if dest != x1 && dest != x2 && dest != x3 {
+                        break;
+                    }
+                }
+            }
+
+            // set the new current cup
+            let node = next.get_at(x3);
+            next.set_at(current, node);
+            current = node as usize;
+
+            // place the picked cups after the destination cup
+            let node = next.get_at(dest);
+            next.set_at(dest, x1 as _);
+            next.set_at(x3, node);
+        }
+    })?;
+    Ok((next[1] as u64) * (next[next[1] as usize] as u64))
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (u64, u64) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[test]
+fn test_day23_part1() {
+    assert_eq!(part1(input()), 89573246);
+}
+
+#[test]
+fn test_day23_part2() {
+    assert_eq!(part2(input()), 2029056128);
+}
+
+#[test]
+fn test_day23_nine_cups() {
+    // with no cups past the input's, part 2 plays the same game as part 1
+    let params = Params {
+        moves2: 100,
+        cups: 9,
+        ..Params::default()
+    };
+    assert_eq!(part2_with(input(), &params), 8 * 9); // 1 is followed by 8 and 9 in 89573246
+    let mut set = Params::default();
+    assert!(params::Params::set(&mut set, "cups", "9").is_ok());
+    assert!(params::Params::set(&mut set, "cups", "8").is_err());
+    assert!(params::Params::set(&mut set, "cups", "4294967296").is_err()); // past the labels
+}
+
+#[test]
+fn test_day23_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day23_progress() {
+    let params = Params {
+        moves2: 1_000_000,
+        ..Params::default()
+    };
+    let mut last = (0, 0);
+    let mut progress = |done, total| {
+        last = (done, total);
+        true
+    };
+    let answer = part2_with_progress(input(), &params, &mut progress);
+    assert_eq!(answer, Ok(part2_with(input(), &params)));
+    assert_eq!(last, (1_000_000, 1_000_000));
+    let mut stop = |done, _| done < 100_000;
+    let answer = part2_with_progress(input(), &params, &mut stop);
+    assert_eq!(answer, Err(Cancelled));
+}
@@ -0,0 +1,396 @@
+use std::io::{self, BufRead};
+use std::iter;
+
+use crate::params::{self, parse_value, ParamError};
+use crate::utils::*;
+
+const MAX_DAYS: usize = 100; // the grid leaves room for the pattern to grow this long
+
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    pub days: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { days: MAX_DAYS }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+        match key {
+            "days" => match parse_value(key, value)? {
+                days if days <= MAX_DAYS => self.days = days,
+                _ => {
+                    return Err(ParamError::OutOfRange(
+                        key.into(),
+                        value.into(),
+                        format!("at most {}", MAX_DAYS),
+                    ))
+                }
+            },
+            _ => return Err(ParamError::UnknownKey(key.into())),
+        }
+        Ok(())
+    }
+
+    fn values(&self) -> Vec<(&'static str, String)> {
+        vec![("days", self.days.to_string())]
+    }
+}
+
+use packed_simd_2::u8x64;
+use rustc_hash::FxHashMap;
+
+type Coord = i16;
+
+#[inline]
+pub fn input() -> &'static [u8] {
+    include_bytes!("input.txt")
+}
+
+#[inline]
+fn parse_coords(s: &[u8]) -> impl Iterator<Item = (Coord, Coord)> + '_ {
+    // these are yielded in what's apparently called 'doublewidth coordinate' format
+    let (mut x, mut y) = (0, 0);
+    let mut s = s;
+    iter::from_fn(move || {
+        while !s.is_empty() {
+            match s.get_first() {
+                b'n' => {
+                    y += 1;
+                    x += 1 - 2 * (s.get_at(1) == b'w') as Coord;
+                    s = s.advance(1);
+                }
+                b's' => {
+                    y -= 1;
+                    x += 1 - 2 * (s.get_at(1) == b'w') as Coord;
+                    s = s.advance(1);
+                }
+                b'e' => {
+                    x += 2;
+                }
+                b'w' => {
+                    x -= 2;
+                }
+                _ => {
+                    let out = (x, y);
+                    x = 0;
+                    y = 0;
+                    s = s.advance(1);
+                    return Some(out);
+                }
+            }
+            s = s.advance(1);
+        }
+        None
+    })
+}
+
+// the tiles that end up black after all the flips, in doublewidth coordinates
+pub type Parsed = Vec<(Coord, Coord)>;
+
+#[inline]
+pub fn parse(s: &[u8]) -> Parsed {
+    let mut counts = FxHashMap::<_, u8>::with_capacity_and_hasher(1 << 9, Default::default());
+    parse_coords(s).for_each(|coord| *counts.entry(coord).or_default() += 1);
+    counts
+        .into_iter()
+        .filter(|&(_, n)| n % 2 != 0)
+        .map(|(coord, _)| coord)
+        .collect()
+}
+
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    // one line per black tile, going north-east or south-east to its row and then east or
+    // west the rest of the way; tiles flipped back to white are gone, and so is the order
+    let paths = s.iter().map(|&(x, y)| {
+        let ns = if y >= 0 { "n" } else { "s" };
+        let (ew, n) = if x >= y.abs() {
+            ("e", x - y.abs())
+        } else {
+            ("w", y.abs() - x)
+        };
+        let mut path = format!("{}e", ns).repeat(y.unsigned_abs() as usize);
+        path.push_str(&ew.repeat(n as usize / 2));
+        if path.is_empty() {
+            path.push_str("ew"); // the reference tile, so that the line isn't blank
+        }
+        path
+    });
+    to_lines(paths)
+}
+
+#[inline]
+pub fn solve1(s: &Parsed) -> usize {
+    s.len()
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> usize {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn part1_with(s: &[u8], _params: &Params) -> usize {
+    part1(s) // the days only matter for part 2
+}
+
+type Lane = u8x64; // lane type
+const W_LANE: usize = Lane::lanes(); // number of bytes in a lane
+const N_LANE: usize = 4; // number of lanes in a row
+const N: usize = N_LANE * W_LANE; // width / height, without padding
+
+#[inline]
+fn splat(x: u8) -> Lane {
+    Lane::splat(x)
+}
+
+#[inline]
+fn doublewidth_to_oddr((x_dw, y_dw): (Coord, Coord)) -> (Coord, Coord) {
+    // double-width -> cube
+    let x_cube = (x_dw - y_dw) / 2;
+    let z_cube = y_dw;
+    // cube -> oddr
+    let x_oddr = x_cube + (z_cube - (z_cube & 1)) / 2;
+    let y_oddr = z_cube;
+    (x_oddr, y_oddr)
+}
+
+#[derive(Clone, Debug)]
+struct Tiles {
+    states: Grid<u8>,
+    counts: Grid<u8>,
+    y_bounds: (usize, usize),
+}
+
+impl Tiles {
+    pub fn new(coords: impl IntoIterator<Item = (Coord, Coord)>) -> Self {
+        let coords = coords.into_iter().collect::<Vec<_>>();
+        let (xmin, xmax, ymin, ymax) = coords.iter().fold(
+            (Coord::MAX, Coord::MIN, Coord::MAX, Coord::MIN),
+            |(xmin, xmax, ymin, ymax), &(x, y)| {
+                (xmin.min(x), xmax.max(x), ymin.min(y), ymax.max(y))
+            },
+        );
+        // rows are indexed including the padding row on top, so that their parity is the
+        // one the neighbour offsets below expect
+        let (x0, y0) = (
+            -(xmin + xmax) / 2 + N as Coord / 2,
+            -(ymin + ymax) / 2 + N as Coord / 2 + 1,
+        );
+        let (x0, y0) = (x0 - (x0 & 1), y0 - (y0 & 1)); // careful to retain row parity
+        let mut states = Grid::new(N, N, 1, W_LANE, 0u8);
+        for &(x, y) in &coords {
+            let (x, y) = ((x + x0) as isize, (y + y0) as isize - 1);
+            states.set(x, y, states.get(x, y).unwrap_or(0) ^ 1);
+        }
+        let y_bounds = ((ymin + y0) as usize, (ymax + y0) as usize);
+        let counts = Grid::new(N, N, 1, W_LANE, 0u8);
+        Self {
+            states,
+            counts,
+            y_bounds,
+        }
+    }
+
+    #[inline]
+    fn stride(&self) -> usize {
+        self.states.stride()
+    }
+
+    #[inline]
+    pub fn read_states(&self, pos: usize) -> Lane {
+        self.states.read_lane(pos)
+    }
+
+    #[inline]
+    pub fn read_counts(&self, pos: usize) -> Lane {
+        self.counts.read_lane(pos)
+    }
+
+    #[inline]
+    fn write_states(&mut self, pos: usize, states: Lane) {
+        self.states.write_lane(pos, states)
+    }
+
+    #[inline]
+    fn write_counts(&mut self, pos: usize, counts: Lane) {
+        self.counts.write_lane(pos, counts)
+    }
+
+    #[inline]
+    pub fn update_counts(&mut self) {
+        let (ymin, ymax) = self.y_bounds;
+        let stride = self.stride();
+        for y in (ymin - 1)..=(ymax + 1) {
+            let row_parity = y & 1;
+            let y = y * stride;
+            let (u, d) = (y - stride, y + stride);
+            for lane in 0..N_LANE {
+                let x = 1 + lane * W_LANE;
+                let (l, r) = (x - 1, x + 1);
+                let mut counts = splat(0);
+                if row_parity == 0 {
+                    counts += self.read_states(r + y);
+                    counts += self.read_states(x + d);
+                    counts += self.read_states(l + d);
+                    counts += self.read_states(l + y);
+                    counts += self.read_states(l + u);
+                    counts += self.read_states(x + u);
+                } else {
+                    counts += self.read_states(r + y);
+                    counts += self.read_states(r + d);
+                    counts += self.read_states(x + d);
+                    counts += self.read_states(l + y);
+                    counts += self.read_states(x + u);
+                    counts += self.read_states(r + u);
+                }
+                self.write_counts(x + y, counts);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn update_states(&mut self) {
+        let (ymin, ymax) = self.y_bounds;
+        let stride = self.stride();
+        for y in (ymin - 1)..=(ymax + 1) {
+            let y = y * stride;
+            for lane in 0..N_LANE {
+                let pos = y + (1 + lane * W_LANE);
+                let counts = self.read_counts(pos);
+                let old = self.read_states(pos);
+                let new = counts.eq(splat(2)) | (old.eq(splat(1)) & counts.eq(splat(1)));
+                self.write_states(pos, new.select(splat(1), splat(0)))
+            }
+        }
+    }
+
+    #[inline]
+    pub fn step(&mut self) {
+        self.update_counts();
+        self.update_states();
+        self.y_bounds = (self.y_bounds.0 - 1, self.y_bounds.1 + 1);
+    }
+
+    #[inline]
+    pub fn count(&self) -> usize {
+        let mut count = 0;
+        let (ymin, ymax) = self.y_bounds;
+        let stride = self.stride();
+        for y in ymin..=ymax {
+            let y = y * stride;
+            for lane in 0..N_LANE {
+                count += self.read_states(y + (1 + lane * W_LANE)).wrapping_sum() as usize;
+            }
+        }
+        count
+    }
+}
+
+#[inline]
+fn live(black_tiles: impl Iterator<Item = (Coord, Coord)>, days: usize) -> usize {
+    let mut tiles = Tiles::new(black_tiles.map(doublewidth_to_oddr));
+    for _ in 0..days.min(MAX_DAYS) {
+        tiles.step();
+    }
+    tiles.count()
+}
+
+#[inline]
+pub fn solve2(s: &Parsed) -> usize {
+    solve2_with(s, &Params::default())
+}
+
+#[inline]
+pub fn solve2_with(s: &Parsed, params: &Params) -> usize {
+    live(s.iter().copied(), params.days)
+}
+
+#[inline]
+pub fn part2(s: &[u8]) -> usize {
+    solve2(&parse(s))
+}
+
+#[inline]
+pub fn part2_with(s: &[u8], params: &Params) -> usize {
+    solve2_with(&parse(s), params)
+}
+
+#[inline]
+pub fn solve_both(s: &[u8]) -> (usize, usize) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
+#[inline]
+fn read_black_tiles<R: BufRead>(r: R) -> io::Result<impl Iterator<Item = (Coord, Coord)>> {
+    // only the parity of each distinct tile is kept around, not the instructions
+    let mut flipped = FxHashMap::<_, bool>::with_capacity_and_hasher(1 << 9, Default::default());
+    for_each_line(r, |line| {
+        parse_coords(line).for_each(|coord| *flipped.entry(coord).or_default() ^= true)
+    })?;
+    Ok(flipped
+        .into_iter()
+        .filter(|&(_, black)| black)
+        .map(|(coord, _)| coord))
+}
+
+#[inline]
+pub fn part1_reader<R: BufRead>(r: R) -> io::Result<usize> {
+    Ok(read_black_tiles(r)?.count())
+}
+
+#[inline]
+pub fn part2_reader<R: BufRead>(r: R) -> io::Result<usize> {
+    Ok(live(read_black_tiles(r)?, MAX_DAYS))
+}
+
+#[test]
+fn test_day24_part1() {
+    assert_eq!(part1(input()), 341);
+}
+
+#[test]
+fn test_day24_part2() {
+    assert_eq!(part2(input()), 3700);
+}
+
+#[test]
+fn test_day24_unparse() {
+    let s = assert_round_trip(input(), |s| unparse(&parse(s)));
+    let (mut tiles, mut reparsed) = (parse(input()), parse(s.as_bytes()));
+    tiles.sort_unstable();
+    reparsed.sort_unstable();
+    assert_eq!(reparsed, tiles);
+    assert_eq!(unparse(&parse(b"esew\n")), "se\n");
+    assert_eq!(unparse(&parse(b"nwwswee\n")), "ew\n");
+    assert_eq!(unparse(&parse(b"nwwswee\nwwwnw\n")).lines().count(), 2);
+    // a tile flipped twice is white again and isn't written back at all
+    let s = b"esew\nnwwswee\nesew\nnwwswee\nnwwswee\n";
+    assert_eq!(assert_round_trip(s, |s| unparse(&parse(s))), "ew\n");
+    let s = assert_round_trip(input(), |s| unparse(&parse(s)));
+    assert_eq!(part2(s.as_bytes()), part2(input()));
+}
+
+#[test]
+fn test_day24_reader() {
+    use std::io::BufReader;
+    assert_eq!(
+        part1_reader(BufReader::with_capacity(7, input())).unwrap(),
+        341
+    );
+    assert_eq!(
+        part2_reader(BufReader::with_capacity(7, input())).unwrap(),
+        3700
+    );
+}
+
+#[test]
+fn test_day24_params() {
+    assert_eq!(part2_with(input(), &Params { days: 0 }), 341);
+    assert_eq!(part2_with(input(), &Params::default()), 3700);
+}
@@ -2,6 +2,7 @@ use std::iter;
 
 use rustc_hash::FxHashMap;
 
+use crate::registry::Strategy;
 use crate::utils::*;
 
 type N = u64;
@@ -72,22 +73,40 @@ fn babystep_giantstep(g: N, h: N, n: N) -> Option<N> {
         .next()
 }
 
-fn parse_input(mut s: &[u8]) -> (N, N) {
+#[inline]
+fn brute_force(g: N, h: N, n: N) -> Option<N> {
+    // solve (g ^ x) % n = h by trying every x in turn
+    iter_modmul(1, g, n)
+        .take(n as _)
+        .position(|e| e == h)
+        .map(|x| x as N)
+}
+
+// the two public keys
+pub type Parsed = (N, N);
+
+#[inline]
+pub fn parse(mut s: &[u8]) -> Parsed {
     (parse_int_fast(&mut s, 1, 8), parse_int_fast(&mut s, 1, 8))
 }
 
+#[inline]
+pub fn unparse(s: &Parsed) -> String {
+    format!("{}\n{}\n", s.0, s.1)
+}
+
 #[inline]
 pub fn input() -> &'static [u8] {
     include_bytes!("input.txt")
 }
 
 #[inline]
-pub fn part1(s: &[u8]) -> N {
+fn solve(keys: &Parsed, discrete_log: fn(N, N, N) -> Option<N>) -> N {
     const M: N = 20201227;
-    let (public_key_1, public_key_2) = parse_input(s);
-    let loop_size_1 = babystep_giantstep(7, public_key_1, M).unwrap();
+    let &(public_key_1, public_key_2) = keys;
+    let loop_size_1 = discrete_log(7, public_key_1, M).unwrap();
     if cfg!(debug_assertions) {
-        let loop_size_2 = babystep_giantstep(7, public_key_2, M).unwrap();
+        let loop_size_2 = discrete_log(7, public_key_2, M).unwrap();
         let encryption_key_1 = modpow(public_key_2, loop_size_1, M);
         let encryption_key_2 = modpow(public_key_1, loop_size_2, M);
         assert_eq!(encryption_key_1, encryption_key_2);
@@ -95,16 +114,65 @@ pub fn part1(s: &[u8]) -> N {
     modpow(public_key_2, loop_size_1, M)
 }
 
+#[inline]
+pub fn solve1(s: &Parsed) -> N {
+    solve(s, babystep_giantstep)
+}
+
+#[inline]
+pub fn part1(s: &[u8]) -> N {
+    solve1(&parse(s))
+}
+
+#[inline]
+pub fn part1_brute_force(s: &[u8]) -> N {
+    solve(&parse(s), brute_force)
+}
+
+pub static STRATEGIES: &[Strategy] = &[
+    Strategy {
+        name: "bsgs",
+        part: 1,
+        solver: |s| part1(s).to_string(),
+    },
+    Strategy {
+        name: "brute",
+        part: 1,
+        solver: |s| part1_brute_force(s).to_string(),
+    },
+];
+
+#[inline]
+pub fn solve2(_: &Parsed) -> usize {
+    0
+}
+
 #[inline]
 pub fn part2(_: &[u8]) -> usize {
     0
 }
 
+#[inline]
+pub fn solve_both(s: &[u8]) -> (N, usize) {
+    let s = parse(s);
+    (solve1(&s), solve2(&s))
+}
+
 #[test]
 fn test_day25_part1() {
     assert_eq!(part1(input()), 5025281);
 }
 
+#[test]
+fn test_day25_unparse() {
+    assert_lossless_round_trip(input(), |s| unparse(&parse(s)));
+}
+
+#[test]
+fn test_day25_brute_force() {
+    assert_eq!(part1_brute_force(input()), 5025281);
+}
+
 #[test]
 fn test_day25_part2() {
     assert_eq!(part2(input()), 0);